@@ -0,0 +1,89 @@
+use std::path::PathBuf;
+
+use miette::{Diagnostic, NamedSource, SourceSpan};
+use thiserror::Error;
+
+#[derive(Error, Diagnostic, Debug)]
+pub enum AocError {
+    #[error(transparent)]
+    #[diagnostic(code(aoc::io_error))]
+    IoError(#[from] std::io::Error),
+
+    #[error("invalid input at line {line}, column {column}: {message}")]
+    #[diagnostic(code(aoc::parse_error))]
+    ParseError {
+        #[source_code]
+        src: NamedSource,
+        #[label("{message}")]
+        span: SourceSpan,
+        message: String,
+        line: usize,
+        column: usize,
+    },
+
+    #[error("invalid input: {0}")]
+    #[diagnostic(code(aoc::invalid_input))]
+    InvalidInput(String),
+
+    #[error("could not read the puzzle input from {}", path.display())]
+    #[diagnostic(
+        code(aoc::input_not_found),
+        help("pass `--input <file>`, `--input -` to read stdin, or set AOC_INPUT_DIR to a directory with `day-XX/input.txt` files")
+    )]
+    InputNotFound {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("{0}")]
+    #[diagnostic(
+        code(aoc::usage),
+        help("usage: [--input <file>] [--param <name>=<value>]..., `--input -` reads stdin")
+    )]
+    Usage(String),
+
+    #[error("invalid parameter `{name}`: {message}")]
+    #[diagnostic(code(aoc::invalid_param))]
+    InvalidParam { name: String, message: String },
+}
+
+impl AocError {
+    /// Builds a [`AocError::ParseError`] pointing at `fragment`, which has to be a
+    /// subslice of `input`, e.g. the remaining input of a failed nom parser.
+    pub fn parse_error(input: &str, fragment: &str, message: impl Into<String>) -> Self {
+        let offset = (fragment.as_ptr() as usize)
+            .checked_sub(input.as_ptr() as usize)
+            .filter(|offset| offset + fragment.len() <= input.len())
+            .unwrap_or(input.len());
+        Self::parse_error_at(input, offset, fragment.len(), message)
+    }
+
+    /// Builds a [`AocError::ParseError`] for the `len` bytes starting at `offset` of `input`.
+    pub fn parse_error_at(
+        input: &str,
+        offset: usize,
+        len: usize,
+        message: impl Into<String>,
+    ) -> Self {
+        let before = &input[..offset];
+        let line_start = before.rfind('\n').map_or(0, |newline| newline + 1);
+        let line_end = input[offset..]
+            .find('\n')
+            .map_or(input.len(), |newline| offset + newline);
+        let len = len
+            .min(line_end - offset)
+            .max(usize::from(offset < input.len()));
+        Self::ParseError {
+            src: NamedSource::new("input", input.to_string()),
+            span: (offset, len).into(),
+            message: message.into(),
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+        }
+    }
+
+    pub fn invalid_input(message: impl Into<String>) -> Self {
+        Self::InvalidInput(message.into())
+    }
+}
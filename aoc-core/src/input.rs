@@ -0,0 +1,203 @@
+use std::{
+    env,
+    io::Read,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    custom_error::AocError,
+    params::{self, Params},
+};
+
+/// Environment variable pointing at a directory laid out as `day-XX/input.txt`.
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+/// The directory inputs are looked up in, `AOC_INPUT_DIR` or the workspace root.
+pub fn input_dir() -> PathBuf {
+    env::var_os(INPUT_DIR_VAR)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| Path::new(env!("CARGO_MANIFEST_DIR")).join(".."))
+}
+
+/// Files that can hold the input of one part, in the order they are tried.
+///
+/// `day-XX/input.txt` is shared by both parts, `day-XX/input<part>.txt` is the
+/// per part layout the inputs in this repository use. Both parts of a puzzle
+/// get the same input, so an empty per part file falls back to `input1.txt`.
+pub fn input_paths(day: u8, part: u8) -> [PathBuf; 3] {
+    paths_in(&input_dir(), day, part)
+}
+
+fn paths_in(dir: &Path, day: u8, part: u8) -> [PathBuf; 3] {
+    let day_dir = dir.join(format!("day-{day:02}"));
+    [
+        day_dir.join("input.txt"),
+        day_dir.join(format!("input{part}.txt")),
+        day_dir.join("input1.txt"),
+    ]
+}
+
+/// Loads the input of one part from the [`input_dir`].
+pub fn load(day: u8, part: u8) -> Result<String, AocError> {
+    load_from(input_paths(day, part))
+}
+
+fn load_from([shared, per_part, first]: [PathBuf; 3]) -> Result<String, AocError> {
+    let non_empty = |path: &Path| {
+        path.metadata()
+            .is_ok_and(|meta| meta.is_file() && meta.len() > 0)
+    };
+    if shared.is_file() {
+        read(&shared)
+    } else if non_empty(&per_part) {
+        read(&per_part)
+    } else if non_empty(&first) {
+        read(&first)
+    } else if per_part.is_file() {
+        read(&per_part)
+    } else {
+        read(&shared)
+    }
+}
+
+/// Reads the input from `path`, `-` reads stdin.
+pub fn read(path: &Path) -> Result<String, AocError> {
+    if path == Path::new("-") {
+        let mut buffer = String::new();
+        std::io::stdin().read_to_string(&mut buffer)?;
+        return Ok(buffer);
+    }
+    std::fs::read_to_string(path).map_err(|source| AocError::InputNotFound {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+/// The input for a day's binary, from `--input <file>` (`-` for stdin) or else
+/// from the [`input_dir`].
+pub fn from_args(day: u8, part: u8) -> Result<String, AocError> {
+    from_args_with_params::<()>(day, part).map(|(input, ())| input)
+}
+
+/// Like [`from_args`], and also applies every `--param <name>=<value>` to the
+/// default parameters.
+pub fn from_args_with_params<P: Params>(day: u8, part: u8) -> Result<(String, P), AocError> {
+    let args = Args::parse(env::args().skip(1))?;
+    let params = P::with_overrides(
+        args.params
+            .iter()
+            .map(|param| params::parse_override(param))
+            .collect::<Result<Vec<_>, _>>()?,
+    )?;
+    let input = match args.input {
+        Some(path) => read(&path)?,
+        None => load(day, part)?,
+    };
+    Ok((input, params))
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct Args {
+    input: Option<PathBuf>,
+    params: Vec<String>,
+}
+
+impl Args {
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Self, AocError> {
+        let mut parsed = Self::default();
+        while let Some(arg) = args.next() {
+            let mut value = |flag: &str| {
+                args.next()
+                    .ok_or_else(|| AocError::Usage(format!("`{flag}` needs a value")))
+            };
+            match arg.as_str() {
+                "-i" | "--input" => parsed.input = Some(PathBuf::from(value(&arg)?)),
+                "-P" | "--param" => parsed.params.push(value(&arg)?),
+                _ => {
+                    if let Some(path) = arg.strip_prefix("--input=") {
+                        parsed.input = Some(PathBuf::from(path));
+                    } else if let Some(param) = arg.strip_prefix("--param=") {
+                        parsed.params.push(param.to_string());
+                    } else {
+                        return Err(AocError::Usage(format!("unexpected argument `{arg}`")));
+                    }
+                }
+            }
+        }
+        Ok(parsed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> impl Iterator<Item = String> {
+        args.iter()
+            .map(|arg| arg.to_string())
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    fn input(input: Option<&str>) -> Args {
+        Args {
+            input: input.map(PathBuf::from),
+            params: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_load_falls_back_to_part_one() {
+        let dir = env::temp_dir().join(format!("aoc-input-{}", std::process::id()));
+        let day_dir = dir.join("day-10");
+        std::fs::create_dir_all(&day_dir).unwrap();
+        std::fs::write(day_dir.join("input1.txt"), "first").unwrap();
+        std::fs::write(day_dir.join("input2.txt"), "").unwrap();
+        std::fs::write(day_dir.join("input3.txt"), "third").unwrap();
+        assert_eq!("first", load_from(paths_in(&dir, 10, 2)).unwrap());
+        assert_eq!("third", load_from(paths_in(&dir, 10, 3)).unwrap());
+        std::fs::write(day_dir.join("input.txt"), "shared").unwrap();
+        assert_eq!("shared", load_from(paths_in(&dir, 10, 2)).unwrap());
+        assert!(matches!(
+            load_from(paths_in(&dir, 11, 1)),
+            Err(AocError::InputNotFound { .. })
+        ));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_args() {
+        assert_eq!(Args::parse(args(&[])).unwrap(), input(None));
+        assert_eq!(
+            Args::parse(args(&["--input", "mine.txt"])).unwrap(),
+            input(Some("mine.txt"))
+        );
+        assert_eq!(Args::parse(args(&["--input=-"])).unwrap(), input(Some("-")));
+        assert_eq!(
+            Args::parse(args(&[
+                "-P",
+                "steps=6",
+                "-i",
+                "example.txt",
+                "--param=min=7"
+            ]))
+            .unwrap(),
+            Args {
+                input: Some(PathBuf::from("example.txt")),
+                params: vec!["steps=6".to_string(), "min=7".to_string()],
+            }
+        );
+        assert!(matches!(
+            Args::parse(args(&["-i"])),
+            Err(AocError::Usage(_))
+        ));
+        assert!(matches!(
+            Args::parse(args(&["--param"])),
+            Err(AocError::Usage(_))
+        ));
+        assert!(matches!(
+            Args::parse(args(&["input.txt"])),
+            Err(AocError::Usage(_))
+        ));
+    }
+}
@@ -0,0 +1,9 @@
+pub mod custom_error;
+pub mod input;
+pub mod params;
+pub mod parse;
+pub mod solution;
+
+pub use custom_error::AocError;
+pub use params::{Param, Params};
+pub use solution::{DynSolution, Solution};
@@ -0,0 +1,206 @@
+use std::{fmt::Debug, str::FromStr};
+
+use crate::custom_error::AocError;
+
+/// A tunable value of a puzzle, as listed by [`Params::FIELDS`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Param {
+    pub name: &'static str,
+    pub help: &'static str,
+    /// The puzzle's value as written in the source, e.g. `64` or `2e14`
+    pub default: &'static str,
+}
+
+/// Puzzle values that can be overridden by name, e.g. `steps=6` for the example
+/// of day 21. `Default` gives the values from the puzzle statement.
+///
+/// Implement it with [`params!`](crate::params!) rather than by hand.
+pub trait Params: Default + Debug {
+    const FIELDS: &'static [Param];
+
+    fn set(&mut self, name: &str, value: &str) -> Result<(), AocError>;
+
+    /// The defaults with every `(name, value)` pair of `overrides` applied.
+    fn with_overrides<'a>(
+        overrides: impl IntoIterator<Item = (&'a str, &'a str)>,
+    ) -> Result<Self, AocError> {
+        let mut params = Self::default();
+        for (name, value) in overrides {
+            params.set(name, value)?;
+        }
+        Ok(params)
+    }
+}
+
+impl Params for () {
+    const FIELDS: &'static [Param] = &[];
+
+    fn set(&mut self, name: &str, _value: &str) -> Result<(), AocError> {
+        Err(unknown::<Self>(name))
+    }
+}
+
+/// Splits `name=value` into its two halves.
+pub fn parse_override(text: &str) -> Result<(&str, &str), AocError> {
+    text.split_once('=')
+        .map(|(name, value)| (name.trim(), value.trim()))
+        .filter(|(name, value)| !name.is_empty() && !value.is_empty())
+        .ok_or_else(|| AocError::InvalidParam {
+            name: text.to_string(),
+            message: "expected `name=value`".to_string(),
+        })
+}
+
+/// Parses a list of overrides separated by whitespace or commas, e.g. `min=7, max=27`.
+pub fn parse_overrides(text: &str) -> Result<Vec<(&str, &str)>, AocError> {
+    text.split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|item| !item.is_empty())
+        .map(parse_override)
+        .collect()
+}
+
+/// Parses the value of the parameter `name`, `_` separators are allowed in numbers.
+pub fn parse_value<T>(name: &str, value: &str) -> Result<T, AocError>
+where
+    T: FromStr,
+    T::Err: std::fmt::Display,
+{
+    value
+        .replace('_', "")
+        .parse()
+        .map_err(|error| AocError::InvalidParam {
+            name: name.to_string(),
+            message: format!("`{value}` {error}"),
+        })
+}
+
+/// The error for a parameter that `P` does not have.
+pub fn unknown<P: Params>(name: &str) -> AocError {
+    let message = match P::FIELDS {
+        [] => "this part has no parameters".to_string(),
+        fields => format!(
+            "expected one of {}",
+            fields
+                .iter()
+                .map(|field| format!("`{}`", field.name))
+                .collect::<Vec<String>>()
+                .join(", ")
+        ),
+    };
+    AocError::InvalidParam {
+        name: name.to_string(),
+        message,
+    }
+}
+
+/// Declares a struct of puzzle parameters and implements [`Params`] and
+/// `Default` for it. Every field needs a doc comment and a default value.
+///
+/// ```
+/// aoc_core::params! {
+///     /// Number of steps the elf takes from the starting plot
+///     pub struct Steps {
+///         /// Steps taken from `S`
+///         steps: u32 = 64,
+///     }
+/// }
+///
+/// use aoc_core::params::Params;
+/// assert_eq!(6, Steps::with_overrides([("steps", "6")]).unwrap().steps);
+/// ```
+#[macro_export]
+macro_rules! params {
+    (
+        $(#[$meta:meta])*
+        pub struct $name:ident {
+            $(
+                #[doc = $help:literal]
+                $field:ident: $ty:ty = $default:expr
+            ),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        pub struct $name {
+            $(
+                #[doc = $help]
+                pub $field: $ty,
+            )*
+        }
+
+        impl Default for $name {
+            fn default() -> Self {
+                Self {
+                    $($field: $default,)*
+                }
+            }
+        }
+
+        impl $crate::params::Params for $name {
+            const FIELDS: &'static [$crate::params::Param] = &[
+                $(
+                    $crate::params::Param {
+                        name: stringify!($field),
+                        help: $help.trim_ascii(),
+                        default: stringify!($default),
+                    },
+                )*
+            ];
+
+            fn set(&mut self, name: &str, value: &str) -> Result<(), $crate::AocError> {
+                match name {
+                    $(stringify!($field) => self.$field = $crate::params::parse_value(name, value)?,)*
+                    _ => return Err($crate::params::unknown::<Self>(name)),
+                }
+                Ok(())
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    crate::params! {
+        /// The test area of day 24
+        pub struct TestArea {
+            /// Smallest X and Y of a crossing
+            min: f64 = 2e14,
+            /// Largest X and Y of a crossing
+            max: f64 = 4e14,
+        }
+    }
+
+    #[test]
+    fn test_with_overrides() {
+        let area = TestArea::with_overrides(parse_overrides("min=7, max=2_7").unwrap()).unwrap();
+        assert_eq!(
+            TestArea {
+                min: 7.0,
+                max: 27.0
+            },
+            area
+        );
+        assert_eq!("2e14", TestArea::FIELDS[0].default);
+        assert_eq!("Smallest X and Y of a crossing", TestArea::FIELDS[0].help);
+    }
+
+    #[test]
+    fn test_invalid_overrides() {
+        assert!(matches!(
+            TestArea::with_overrides([("steps", "6")]),
+            Err(AocError::InvalidParam { message, .. }) if message == "expected one of `min`, `max`"
+        ));
+        assert!(matches!(
+            TestArea::with_overrides([("min", "seven")]),
+            Err(AocError::InvalidParam { name, .. }) if name == "min"
+        ));
+        assert!(matches!(
+            <()>::with_overrides([("steps", "6")]),
+            Err(AocError::InvalidParam { message, .. }) if message == "this part has no parameters"
+        ));
+        assert!(parse_overrides("steps").is_err());
+        assert!(parse_overrides("").unwrap().is_empty());
+    }
+}
@@ -0,0 +1,131 @@
+use nom::{error::ErrorKind, Parser};
+
+use crate::custom_error::AocError;
+
+/// Runs a nom `parser` over the whole `input` and turns a failure into a
+/// [`AocError::ParseError`] pointing at the offending line and column.
+///
+/// Only whitespace may be left over after the parser is done.
+pub fn finish<'a, O>(
+    input: &'a str,
+    mut parser: impl Parser<&'a str, O, nom::error::Error<&'a str>>,
+) -> Result<O, AocError> {
+    match parser.parse(input) {
+        Ok((rest, output)) if rest.trim().is_empty() => Ok(output),
+        Ok((rest, _)) => Err(AocError::parse_error(
+            input,
+            rest.trim(),
+            "unexpected input",
+        )),
+        Err(nom::Err::Error(error) | nom::Err::Failure(error)) => Err(AocError::parse_error(
+            input,
+            error.input,
+            expected(error.code),
+        )),
+        Err(nom::Err::Incomplete(_)) => Err(AocError::parse_error(
+            input,
+            &input[input.len()..],
+            "unexpected end of input",
+        )),
+    }
+}
+
+/// Splits `input` into its lines and checks they form a non-empty ascii grid
+/// where every row has the same width.
+pub fn grid(input: &str) -> Result<Vec<&str>, AocError> {
+    let lines = input.lines().collect::<Vec<&str>>();
+    let Some(width) = lines.first().map(|line| line.len()) else {
+        return Err(AocError::invalid_input("the grid is empty"));
+    };
+    for line in &lines {
+        if let Some(column) = line.find(|ch: char| !ch.is_ascii()) {
+            return Err(AocError::parse_error(
+                input,
+                &line[column..],
+                "expected an ascii character",
+            ));
+        }
+        if line.len() != width {
+            return Err(AocError::parse_error(
+                input,
+                line,
+                format!("expected {width} columns, found {}", line.len()),
+            ));
+        }
+    }
+    Ok(lines)
+}
+
+/// Same as [`grid`] but every character also has to be one of `tiles`.
+pub fn grid_of<'a>(input: &'a str, tiles: &str) -> Result<Vec<&'a str>, AocError> {
+    let lines = grid(input)?;
+    for line in &lines {
+        if let Some(column) = line.find(|ch| !tiles.contains(ch)) {
+            return Err(AocError::parse_error(
+                input,
+                &line[column..column + 1],
+                format!("expected one of `{tiles}`"),
+            ));
+        }
+    }
+    Ok(lines)
+}
+
+fn expected(kind: ErrorKind) -> String {
+    match kind {
+        ErrorKind::Digit => "expected a number".to_string(),
+        ErrorKind::Alpha => "expected a word".to_string(),
+        ErrorKind::AlphaNumeric => "expected a label".to_string(),
+        ErrorKind::Space | ErrorKind::MultiSpace => "expected a space".to_string(),
+        ErrorKind::CrLf => "expected a line break".to_string(),
+        ErrorKind::Eof => "unexpected input".to_string(),
+        ErrorKind::TooLarge => "the value is too large".to_string(),
+        kind => format!("unexpected input, `{}` failed", kind.description()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nom::{
+        character::complete::{digit1, line_ending},
+        multi::separated_list1,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_finish_points_at_line_and_column() {
+        let error = finish("12\n34\n5x6", separated_list1(line_ending, digit1)).unwrap_err();
+        match error {
+            AocError::ParseError {
+                span, line, column, ..
+            } => {
+                assert_eq!((line, column), (3, 2));
+                assert_eq!((span.offset(), span.len()), (7, 2));
+            }
+            error => panic!("expected a parse error, got {error:?}"),
+        }
+    }
+
+    #[test]
+    fn test_grid_rejects_ragged_rows() {
+        assert!(matches!(grid(""), Err(AocError::InvalidInput(_))));
+        assert!(matches!(
+            grid("...\n..\n..."),
+            Err(AocError::ParseError {
+                line: 2,
+                column: 1,
+                ..
+            })
+        ));
+        assert_eq!(grid("#.\n.#\n").unwrap(), vec!["#.", ".#"]);
+        assert!(matches!(
+            grid_of("#.\n.O", "#."),
+            Err(AocError::ParseError {
+                line: 2,
+                column: 2,
+                ..
+            })
+        ));
+    }
+}
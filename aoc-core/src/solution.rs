@@ -0,0 +1,95 @@
+use std::{
+    fmt::Debug,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    custom_error::AocError,
+    params::{Param, Params},
+};
+
+/// One part of one day's puzzle.
+///
+/// Implementors are unit structs (`day_05::part2::Part2`) so they can be put
+/// into a registry as `&dyn DynSolution` through the blanket impl below.
+pub trait Solution: Sync {
+    const DAY: u8;
+    const PART: u8;
+    /// The puzzle title from adventofcode.com
+    const TITLE: &'static str;
+
+    /// The parsed puzzle input, it may borrow from the input text.
+    type Model<'a>;
+    /// Tunable puzzle values, `Default` gives the ones from the puzzle statement.
+    type Params: Params;
+
+    fn parse(input: &str) -> Result<Self::Model<'_>, AocError>;
+
+    fn solve(model: &Self::Model<'_>, params: &Self::Params) -> Result<String, AocError>;
+
+    fn process(input: &str, params: &Self::Params) -> Result<String, AocError> {
+        Self::solve(&Self::parse(input)?, params)
+    }
+}
+
+/// Object safe view of a [`Solution`], parameters are given by name and
+/// everything that is not overridden keeps its default.
+pub trait DynSolution: Sync {
+    fn day(&self) -> u8;
+    fn part(&self) -> u8;
+    fn title(&self) -> &'static str;
+    fn params(&self) -> &'static [Param];
+    fn process_with(&self, input: &str, overrides: &[(&str, &str)]) -> Result<String, AocError>;
+
+    fn process(&self, input: &str) -> Result<String, AocError> {
+        self.process_with(input, &[])
+    }
+
+    /// Runs `parse` and then `solve` with the default parameters, returning
+    /// how long each of them took.
+    fn time_phases(&self, input: &str) -> Result<(Duration, Duration), AocError>;
+}
+
+impl<S: Solution> DynSolution for S {
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn part(&self) -> u8 {
+        S::PART
+    }
+
+    fn title(&self) -> &'static str {
+        S::TITLE
+    }
+
+    fn params(&self) -> &'static [Param] {
+        S::Params::FIELDS
+    }
+
+    fn process_with(&self, input: &str, overrides: &[(&str, &str)]) -> Result<String, AocError> {
+        S::process(
+            input,
+            &S::Params::with_overrides(overrides.iter().copied())?,
+        )
+    }
+
+    fn time_phases(&self, input: &str) -> Result<(Duration, Duration), AocError> {
+        let params = S::Params::default();
+        let start = Instant::now();
+        let model = std::hint::black_box(S::parse(std::hint::black_box(input))?);
+        let parsed = start.elapsed();
+        std::hint::black_box(S::solve(&model, &params)?);
+        Ok((parsed, start.elapsed() - parsed))
+    }
+}
+
+impl Debug for dyn DynSolution {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DynSolution")
+            .field("day", &self.day())
+            .field("part", &self.part())
+            .field("title", &self.title())
+            .finish()
+    }
+}
@@ -0,0 +1,437 @@
+//! Timing runs of the registered solutions, stored as JSON in `benchmarks.json`
+//! and rendered into `benchmarks.txt`.
+
+use std::{
+    collections::BTreeMap,
+    fmt::Write,
+    time::{Duration, Instant},
+};
+
+use aoc_core::{AocError, DynSolution};
+use serde::{Deserialize, Serialize};
+
+/// Every recorded run, oldest first.
+#[derive(Serialize, Deserialize, Default, Debug)]
+pub struct History {
+    pub runs: Vec<Run>,
+}
+
+/// The timings of one commit, one [`Measurement`] per day and part.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Run {
+    pub commit: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub machine: Option<String>,
+    pub results: Vec<Measurement>,
+}
+
+/// What part of a solution a [`Measurement`] times, the same split as the
+/// `partN`, `partN_parse` and `partN_solve` divan benches.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum Phase {
+    /// `parse` and `solve` together
+    #[default]
+    Process,
+    Parse,
+    Solve,
+}
+
+impl Phase {
+    /// The divan bench name of the phase for one part, e.g. `part1_parse`.
+    pub fn bench_name(self, part: u8) -> String {
+        match self {
+            Phase::Process => format!("part{part}"),
+            Phase::Parse => format!("part{part}_parse"),
+            Phase::Solve => format!("part{part}_solve"),
+        }
+    }
+}
+
+/// Timings of one phase of a part on the real input, all in nanoseconds.
+/// Runs recorded before phases were measured only hold `process`.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Measurement {
+    pub day: u8,
+    pub part: u8,
+    #[serde(default)]
+    pub phase: Phase,
+    pub samples: u32,
+    pub fastest: u64,
+    pub slowest: u64,
+    pub median: u64,
+    pub mean: u64,
+}
+
+/// How the median of one part moved between two runs.
+#[derive(Debug, PartialEq)]
+pub enum Change {
+    /// Slower by more than the threshold.
+    Regression(f64),
+    /// Faster by more than the threshold.
+    Improvement(f64),
+    /// Within the threshold.
+    Unchanged(f64),
+    /// Only measured in the newer run.
+    Added,
+    /// Only measured in the older run.
+    Removed,
+}
+
+impl Measurement {
+    /// What identifies the measurement within a run.
+    pub fn key(&self) -> (u8, u8, Phase) {
+        (self.day, self.part, self.phase)
+    }
+
+    fn from_times(day: u8, part: u8, phase: Phase, mut times: Vec<u64>) -> Self {
+        times.sort_unstable();
+        Measurement {
+            day,
+            part,
+            phase,
+            samples: times.len() as u32,
+            fastest: times[0],
+            slowest: times[times.len() - 1],
+            median: times[times.len() / 2],
+            mean: times.iter().sum::<u64>() / times.len() as u64,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Comparison {
+    pub day: u8,
+    pub part: u8,
+    pub phase: Phase,
+    pub base: Option<Measurement>,
+    pub head: Option<Measurement>,
+    pub change: Change,
+}
+
+impl History {
+    /// The run of `commit`, which may be a prefix of the stored hash.
+    pub fn find(&self, commit: &str) -> Option<&Run> {
+        self.runs
+            .iter()
+            .rev()
+            .find(|run| run.commit.starts_with(commit))
+    }
+
+    /// Stores `run`, measurements of a commit that was already recorded replace
+    /// the old ones for the same day, part and phase and keep the others.
+    pub fn record(&mut self, run: Run) {
+        match self.runs.iter_mut().find(|old| old.commit == run.commit) {
+            Some(old) => {
+                old.machine = run.machine.or(old.machine.take());
+                for measurement in run.results {
+                    old.results
+                        .retain(|result| result.key() != measurement.key());
+                    old.results.push(measurement);
+                }
+                old.results.sort_by_key(Measurement::key);
+            }
+            None => self.runs.push(run),
+        }
+    }
+}
+
+/// Runs `solution` on `input` up to `samples` times, stopping early once
+/// `max_time` is spent. Slow parts still get a single sample.
+///
+/// Every sample times `parse` and `solve` on their own, `process` is the sum
+/// of both within the same sample. Returns the measurements of `process`,
+/// `parse` and `solve` in that order.
+pub fn measure(
+    solution: &dyn DynSolution,
+    input: &str,
+    samples: u32,
+    max_time: Duration,
+) -> Result<[Measurement; 3], AocError> {
+    let start = Instant::now();
+    let (mut parse, mut solve) = (Vec::new(), Vec::new());
+    while parse.is_empty() || (parse.len() < samples as usize && start.elapsed() < max_time) {
+        let (parsed, solved) = solution.time_phases(input)?;
+        parse.push(parsed.as_nanos() as u64);
+        solve.push(solved.as_nanos() as u64);
+    }
+    let process = parse.iter().zip(&solve).map(|(a, b)| a + b).collect();
+    let measurement =
+        |phase, times| Measurement::from_times(solution.day(), solution.part(), phase, times);
+    Ok([
+        measurement(Phase::Process, process),
+        measurement(Phase::Parse, parse),
+        measurement(Phase::Solve, solve),
+    ])
+}
+
+/// Compares the medians of every part and phase measured in either run, a
+/// change of more than `threshold` percent counts as a regression or an
+/// improvement.
+pub fn compare(base: &Run, head: &Run, threshold: f64) -> Vec<Comparison> {
+    type Pair = (Option<Measurement>, Option<Measurement>);
+    let mut parts = BTreeMap::<(u8, u8, Phase), Pair>::new();
+    for result in &base.results {
+        parts.entry(result.key()).or_default().0 = Some(*result);
+    }
+    for result in &head.results {
+        parts.entry(result.key()).or_default().1 = Some(*result);
+    }
+    parts
+        .into_iter()
+        .map(|((day, part, phase), (base, head))| {
+            let change = match (base, head) {
+                (Some(base), Some(head)) => {
+                    let percent = (head.median as f64 - base.median as f64)
+                        / base.median.max(1) as f64
+                        * 100.0;
+                    if percent > threshold {
+                        Change::Regression(percent)
+                    } else if percent < -threshold {
+                        Change::Improvement(percent)
+                    } else {
+                        Change::Unchanged(percent)
+                    }
+                }
+                (None, _) => Change::Added,
+                (_, None) => Change::Removed,
+            };
+            Comparison {
+                day,
+                part,
+                phase,
+                base,
+                head,
+                change,
+            }
+        })
+        .collect()
+}
+
+/// Renders the comparison as a table, one row per part and phase.
+pub fn comparison_table(base: &Run, head: &Run, comparisons: &[Comparison]) -> String {
+    let mut table = format!(
+        "{:<4} {:<12} {:<12} {:<12} {:<9} status\n",
+        "day",
+        "bench",
+        short(&base.commit),
+        short(&head.commit),
+        "change"
+    );
+    for comparison in comparisons {
+        let median = |measurement: Option<Measurement>| {
+            measurement.map_or(String::from("-"), |measurement| {
+                format_time(measurement.median)
+            })
+        };
+        let (change, status) = match comparison.change {
+            Change::Regression(percent) => (format!("{percent:+.1}%"), "REGRESSION"),
+            Change::Improvement(percent) => (format!("{percent:+.1}%"), "improved"),
+            Change::Unchanged(percent) => (format!("{percent:+.1}%"), "ok"),
+            Change::Added => (String::new(), "new"),
+            Change::Removed => (String::new(), "not measured"),
+        };
+        writeln!(
+            table,
+            "{:<4} {:<12} {:<12} {:<12} {change:<9} {status}",
+            comparison.day,
+            comparison.phase.bench_name(comparison.part),
+            median(comparison.base),
+            median(comparison.head)
+        )
+        .unwrap();
+    }
+    table
+}
+
+/// Renders a run in the layout of divan's output, which is what `benchmarks.txt`
+/// used to be copied from.
+pub fn render(run: &Run) -> String {
+    let mut text = format!("* Commit: {}\n", run.commit);
+    if let Some(machine) = &run.machine {
+        writeln!(text, "* Machine: {machine}").unwrap();
+    }
+    for results in run.results.chunk_by(|a, b| a.day == b.day) {
+        writeln!(
+            text,
+            "\n{:<14} {:<13} │ {:<13} │ {:<13} │ {:<13} │ samples",
+            format!("day_{:02}", results[0].day),
+            "fastest",
+            "slowest",
+            "median",
+            "mean"
+        )
+        .unwrap();
+        for (idx, result) in results.iter().enumerate() {
+            let branch = if idx + 1 == results.len() {
+                '╰'
+            } else {
+                '├'
+            };
+            writeln!(
+                text,
+                "{branch}─ {:<11} {:<13} │ {:<13} │ {:<13} │ {:<13} │ {}",
+                result.phase.bench_name(result.part),
+                format_time(result.fastest),
+                format_time(result.slowest),
+                format_time(result.median),
+                format_time(result.mean),
+                result.samples
+            )
+            .unwrap();
+        }
+    }
+    text
+}
+
+/// Formats nanoseconds with four significant digits like divan does, e.g. `33.29 µs`.
+pub fn format_time(nanos: u64) -> String {
+    let (value, unit) = match nanos {
+        0..=999 => return format!("{nanos} ns"),
+        1_000..=999_999 => (nanos as f64 / 1e3, "µs"),
+        1_000_000..=999_999_999 => (nanos as f64 / 1e6, "ms"),
+        _ => (nanos as f64 / 1e9, "s"),
+    };
+    let decimals = 3usize.saturating_sub(value.log10().floor() as usize);
+    let value = format!("{value:.decimals$}");
+    let value = match value.contains('.') {
+        true => value.trim_end_matches('0').trim_end_matches('.'),
+        false => &value,
+    };
+    format!("{value} {unit}")
+}
+
+fn short(commit: &str) -> &str {
+    commit.get(..12).unwrap_or(commit)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(commit: &str, medians: &[(u8, u8, u64)]) -> Run {
+        phased_run(
+            commit,
+            &medians
+                .iter()
+                .map(|&(day, part, median)| (day, part, Phase::Process, median))
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    fn phased_run(commit: &str, medians: &[(u8, u8, Phase, u64)]) -> Run {
+        Run {
+            commit: commit.to_string(),
+            machine: None,
+            results: medians
+                .iter()
+                .map(|&(day, part, phase, median)| Measurement {
+                    day,
+                    part,
+                    phase,
+                    samples: 1,
+                    fastest: median,
+                    slowest: median,
+                    median,
+                    mean: median,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_format_time() {
+        assert_eq!("999 ns", format_time(999));
+        assert_eq!("33.29 µs", format_time(33_290));
+        assert_eq!("497 µs", format_time(497_000));
+        assert_eq!("1.156 ms", format_time(1_156_000));
+        assert_eq!("320 s", format_time(320_000_000_000));
+    }
+
+    #[test]
+    fn test_compare() {
+        let base = run("a", &[(1, 1, 100), (1, 2, 100), (2, 1, 100), (3, 1, 100)]);
+        let head = run("b", &[(1, 1, 105), (1, 2, 150), (2, 1, 50), (4, 1, 100)]);
+        let changes = compare(&base, &head, 10.0)
+            .into_iter()
+            .map(|comparison| comparison.change)
+            .collect::<Vec<Change>>();
+        assert_eq!(
+            vec![
+                Change::Unchanged(5.0),
+                Change::Regression(50.0),
+                Change::Improvement(-50.0),
+                Change::Removed,
+                Change::Added,
+            ],
+            changes
+        );
+    }
+
+    #[test]
+    fn test_record_merges_runs_of_the_same_commit() {
+        let mut history = History::default();
+        history.record(run("a", &[(1, 1, 100), (1, 2, 100)]));
+        history.record(run("a", &[(1, 2, 200), (2, 1, 300)]));
+        history.record(run("b", &[(1, 1, 100)]));
+        assert_eq!(2, history.runs.len());
+        assert_eq!(
+            &run("a", &[(1, 1, 100), (1, 2, 200), (2, 1, 300)]),
+            history.find("a").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_render() {
+        let text = render(&phased_run(
+            "a",
+            &[
+                (1, 1, Phase::Process, 33_290),
+                (1, 1, Phase::Parse, 3_000),
+                (1, 1, Phase::Solve, 30_290),
+                (1, 2, Phase::Process, 1_156_000),
+                (2, 1, Phase::Process, 999),
+            ],
+        ));
+        assert_eq!(
+            "* Commit: a
+
+day_01         fastest       │ slowest       │ median        │ mean          │ samples
+├─ part1       33.29 µs      │ 33.29 µs      │ 33.29 µs      │ 33.29 µs      │ 1
+├─ part1_parse 3 µs          │ 3 µs          │ 3 µs          │ 3 µs          │ 1
+├─ part1_solve 30.29 µs      │ 30.29 µs      │ 30.29 µs      │ 30.29 µs      │ 1
+╰─ part2       1.156 ms      │ 1.156 ms      │ 1.156 ms      │ 1.156 ms      │ 1
+
+day_02         fastest       │ slowest       │ median        │ mean          │ samples
+╰─ part1       999 ns        │ 999 ns        │ 999 ns        │ 999 ns        │ 1
+",
+            text
+        );
+    }
+
+    #[test]
+    fn test_compare_phases() {
+        let base = phased_run("a", &[(1, 1, Phase::Parse, 100), (1, 1, Phase::Solve, 100)]);
+        let head = phased_run("b", &[(1, 1, Phase::Parse, 100), (1, 1, Phase::Solve, 200)]);
+        let changes = compare(&base, &head, 10.0)
+            .into_iter()
+            .map(|comparison| (comparison.phase, comparison.change))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                (Phase::Parse, Change::Unchanged(0.0)),
+                (Phase::Solve, Change::Regression(100.0)),
+            ],
+            changes
+        );
+    }
+
+    #[test]
+    fn test_old_runs_are_process() {
+        let measurement: Measurement = serde_json::from_str(
+            r#"{"day":1,"part":2,"samples":1,"fastest":1,"slowest":1,"median":1,"mean":1}"#,
+        )
+        .unwrap();
+        assert_eq!(Phase::Process, measurement.phase);
+    }
+}
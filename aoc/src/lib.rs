@@ -0,0 +1,82 @@
+pub mod bench;
+
+use aoc_core::DynSolution;
+
+/// Every implemented puzzle part, in calendar order.
+///
+/// Parts that are still `todo!()` are left out so running the whole calendar
+/// does not panic halfway through, and so are the part 2 copies of part 1 on
+/// days 20 and 21 so their part 1 answers are not reported as part 2.
+pub static SOLUTIONS: &[&dyn DynSolution] = &[
+    &day_01::part1::Part1,
+    &day_01::part2::Part2,
+    &day_02::part1::Part1,
+    &day_02::part2::Part2,
+    &day_03::part1::Part1,
+    &day_03::part2::Part2,
+    &day_04::part1::Part1,
+    &day_04::part2::Part2,
+    &day_05::part1::Part1,
+    &day_05::part2::Part2,
+    &day_06::part1::Part1,
+    &day_06::part2::Part2,
+    &day_07::part1::Part1,
+    &day_07::part2::Part2,
+    &day_08::part1::Part1,
+    &day_08::part2::Part2,
+    &day_09::part1::Part1,
+    &day_09::part2::Part2,
+    &day_10::part1::Part1,
+    &day_10::part2::Part2,
+    &day_11::part1::Part1,
+    &day_11::part2::Part2,
+    &day_12::part1::Part1,
+    &day_12::part2::Part2,
+    &day_13::part1::Part1,
+    &day_13::part2::Part2,
+    &day_14::part1::Part1,
+    &day_14::part2::Part2,
+    &day_15::part1::Part1,
+    &day_15::part2::Part2,
+    &day_16::part1::Part1,
+    &day_16::part2::Part2,
+    &day_18::part1::Part1,
+    &day_18::part2::Part2,
+    &day_19::part1::Part1,
+    &day_19::part2::Part2,
+    &day_20::part1::Part1,
+    &day_21::part1::Part1,
+    &day_22::part1::Part1,
+    &day_22::part2::Part2,
+    &day_23::part1::Part1,
+    &day_23::part2::Part2,
+    &day_24::part1::Part1,
+    &day_25::part1::Part1,
+];
+
+/// Looks up a single part in [`SOLUTIONS`].
+pub fn find(day: u8, part: u8) -> Option<&'static dyn DynSolution> {
+    SOLUTIONS
+        .iter()
+        .copied()
+        .find(|solution| solution.day() == day && solution.part() == part)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solutions_are_unique_and_ordered() {
+        assert!(SOLUTIONS
+            .windows(2)
+            .all(|pair| (pair[0].day(), pair[0].part()) < (pair[1].day(), pair[1].part())));
+    }
+
+    #[test]
+    fn test_find() {
+        let solution = find(14, 2).expect("day 14 part 2 is registered");
+        assert_eq!("Parabolic Reflector Dish", solution.title());
+        assert!(find(17, 1).is_none());
+    }
+}
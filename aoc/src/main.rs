@@ -0,0 +1,344 @@
+use std::{
+    ops::RangeInclusive,
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+use aoc::{
+    bench::{self, Change, History, Run},
+    SOLUTIONS,
+};
+use clap::{Parser, Subcommand};
+use miette::miette;
+
+#[derive(Parser, Debug)]
+#[command(about = "Runs the Advent of Code 2023 solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Run one day, a range of days or the whole calendar
+    Run {
+        /// A single day (`14`) or an inclusive range (`3-7`), defaults to every day
+        #[arg(short, long, default_value = "1-25")]
+        day: Days,
+        /// Only run this part
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+        /// Puzzle input file, `-` reads stdin. Defaults to `$AOC_INPUT_DIR/day-XX/input.txt`
+        #[arg(short, long)]
+        input: Option<PathBuf>,
+        /// Override a puzzle parameter, e.g. `--param steps=6` to run the day 21 example
+        #[arg(short = 'P', long = "param", value_name = "NAME=VALUE")]
+        params: Vec<String>,
+    },
+    /// Time the solutions, compare recorded runs and regenerate `benchmarks.txt`
+    Bench {
+        #[command(subcommand)]
+        command: BenchCommand,
+        /// Where the runs are recorded, keyed by git commit
+        #[arg(long, global = true, default_value = "benchmarks.json")]
+        history: PathBuf,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum BenchCommand {
+    /// Time every selected part on its input and record the results under the current commit
+    Record {
+        /// A single day (`14`) or an inclusive range (`3-7`), defaults to every day
+        #[arg(short, long, default_value = "1-25")]
+        day: Days,
+        /// Only time this part
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+        /// How many times each part is run
+        #[arg(long, default_value_t = 100)]
+        samples: u32,
+        /// Stop sampling a part after this many seconds, it always runs at least once
+        #[arg(long, default_value_t = 5.0)]
+        max_time: f64,
+        /// Record under this commit instead of `git rev-parse HEAD`
+        #[arg(long)]
+        commit: Option<String>,
+        /// Description of the machine, shown at the top of `benchmarks.txt`
+        #[arg(long)]
+        machine: Option<String>,
+    },
+    /// Compare the medians of two recorded commits, failing on regressions
+    Compare {
+        /// The older commit, or a prefix of it
+        base: String,
+        /// The newer commit, defaults to the latest recorded run
+        head: Option<String>,
+        /// Percentage a median may grow before it counts as a regression
+        #[arg(long, default_value_t = 10.0)]
+        threshold: f64,
+    },
+    /// Render a recorded commit into `benchmarks.txt`
+    Report {
+        /// Defaults to the latest recorded run
+        commit: Option<String>,
+        #[arg(short, long, default_value = "benchmarks.txt")]
+        output: PathBuf,
+    },
+}
+
+#[derive(Clone, Debug)]
+struct Days(RangeInclusive<u8>);
+
+impl FromStr for Days {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse_day = |day: &str| match day.trim().parse::<u8>() {
+            Ok(day @ 1..=25) => Ok(day),
+            _ => Err(format!("`{day}` is not a day between 1 and 25")),
+        };
+        let days = match s.split_once('-') {
+            Some((start, end)) => parse_day(start)?..=parse_day(end)?,
+            None => parse_day(s).map(|day| day..=day)?,
+        };
+        if days.is_empty() {
+            return Err(format!("`{s}` is an empty range"));
+        }
+        Ok(Days(days))
+    }
+}
+
+fn read_input(day: u8, part: u8, input: Option<&Path>) -> miette::Result<String> {
+    let file = match input {
+        Some(path) => aoc_core::input::read(path),
+        None => aoc_core::input::load(day, part),
+    };
+    Ok(file?)
+}
+
+fn run(
+    days: Days,
+    part: Option<u8>,
+    input: Option<PathBuf>,
+    params: Vec<String>,
+) -> miette::Result<()> {
+    if input.is_some() && days.0.start() != days.0.end() {
+        return Err(miette!(
+            "--input can only be used together with a single --day"
+        ));
+    }
+    // stdin can only be drained once, so every selected part shares what was read
+    let stdin_input = match input.as_deref() {
+        Some(path) if path == Path::new("-") => Some(aoc_core::input::read(path)?),
+        _ => None,
+    };
+    let overrides = params
+        .iter()
+        .map(|param| aoc_core::params::parse_override(param))
+        .collect::<Result<Vec<_>, _>>()?;
+    let mut failed = 0;
+    let mut total = Duration::ZERO;
+    for solution in SOLUTIONS.iter().filter(|solution| {
+        days.0.contains(&solution.day()) && part.is_none_or(|part| part == solution.part())
+    }) {
+        let file = match &stdin_input {
+            Some(buffer) => Ok(buffer.clone()),
+            None => read_input(solution.day(), solution.part(), input.as_deref()),
+        };
+        let start = Instant::now();
+        let result =
+            file.and_then(|file| solution.process_with(&file, &overrides).map_err(Into::into));
+        let elapsed = start.elapsed();
+        total += elapsed;
+        match result {
+            Ok(answer) => println!(
+                "day {:02} part {} ({}): {answer} ({elapsed:.2?})",
+                solution.day(),
+                solution.part(),
+                solution.title()
+            ),
+            Err(report) => {
+                failed += 1;
+                eprintln!(
+                    "day {:02} part {} ({}): {report:?}",
+                    solution.day(),
+                    solution.part(),
+                    solution.title()
+                );
+            }
+        }
+    }
+    println!("total: {total:.2?}");
+    match failed {
+        0 => Ok(()),
+        failed => Err(miette!("{failed} solution(s) failed")),
+    }
+}
+
+fn load_history(path: &Path) -> miette::Result<History> {
+    match std::fs::read_to_string(path) {
+        Ok(json) => serde_json::from_str(&json)
+            .map_err(|error| miette!("{} is not a benchmark history: {error}", path.display())),
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(History::default()),
+        Err(error) => Err(miette!("could not read {}: {error}", path.display())),
+    }
+}
+
+/// Finds a recorded run by commit prefix or by anything `git rev-parse` understands, like `HEAD~3`.
+fn find_run<'a>(history: &'a History, commit: Option<&str>) -> miette::Result<&'a Run> {
+    match commit {
+        Some(commit) => history
+            .find(commit)
+            .or_else(|| {
+                git(&["rev-parse", "--verify", "--quiet", commit])
+                    .ok()
+                    .and_then(|hash| history.find(&hash))
+            })
+            .ok_or_else(|| miette!("no benchmarks were recorded for commit `{commit}`")),
+        None => history
+            .runs
+            .last()
+            .ok_or_else(|| miette!("no benchmarks were recorded yet")),
+    }
+}
+
+fn git(args: &[&str]) -> miette::Result<String> {
+    let output = std::process::Command::new("git")
+        .args(args)
+        .output()
+        .map_err(|error| miette!("could not run git: {error}"))?;
+    if !output.status.success() {
+        return Err(miette!(
+            "`git {}` failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// The checked out commit, with a `-dirty` suffix when the tree has local
+/// changes. The recorded runs in `history` and the `benchmarks.txt` rendered
+/// from them do not count, so recording several days one after another keeps
+/// them in the same run.
+fn current_commit(history: &Path) -> miette::Result<String> {
+    let commit = git(&["rev-parse", "HEAD"])?;
+    let history = format!(
+        ":(exclude,glob)**/{}",
+        history.file_name().unwrap_or_default().to_string_lossy()
+    );
+    let status = git(&[
+        "status",
+        "--porcelain",
+        "--untracked-files=no",
+        "--",
+        ".",
+        &history,
+        ":(exclude,glob)**/benchmarks.txt",
+    ])?;
+    match status.is_empty() {
+        true => Ok(commit),
+        false => Ok(format!("{commit}-dirty")),
+    }
+}
+
+fn bench(command: BenchCommand, history_path: &Path) -> miette::Result<()> {
+    let mut history = load_history(history_path)?;
+    match command {
+        BenchCommand::Record {
+            day,
+            part,
+            samples,
+            max_time,
+            commit,
+            machine,
+        } => {
+            let commit = match commit {
+                Some(commit) => commit,
+                None => current_commit(history_path)?,
+            };
+            let mut results = Vec::new();
+            for solution in SOLUTIONS.iter().filter(|solution| {
+                day.0.contains(&solution.day()) && part.is_none_or(|part| part == solution.part())
+            }) {
+                let input = match aoc_core::input::load(solution.day(), solution.part()) {
+                    Ok(input) => input,
+                    Err(error) => {
+                        eprintln!(
+                            "day {:02} part {}: skipped, {error}",
+                            solution.day(),
+                            solution.part()
+                        );
+                        continue;
+                    }
+                };
+                let measurements = bench::measure(
+                    *solution,
+                    &input,
+                    samples,
+                    Duration::from_secs_f64(max_time),
+                )?;
+                for measurement in measurements {
+                    println!(
+                        "day {:02} {}: median {} over {} samples",
+                        measurement.day,
+                        measurement.phase.bench_name(measurement.part),
+                        bench::format_time(measurement.median),
+                        measurement.samples
+                    );
+                    results.push(measurement);
+                }
+            }
+            history.record(Run {
+                commit,
+                machine,
+                results,
+            });
+            let json = serde_json::to_string_pretty(&history).expect("the history serializes");
+            std::fs::write(history_path, json + "\n")
+                .map_err(|error| miette!("could not write {}: {error}", history_path.display()))
+        }
+        BenchCommand::Compare {
+            base,
+            head,
+            threshold,
+        } => {
+            let base = find_run(&history, Some(&base))?;
+            let head = find_run(&history, head.as_deref())?;
+            let comparisons = bench::compare(base, head, threshold);
+            print!("{}", bench::comparison_table(base, head, &comparisons));
+            match comparisons
+                .iter()
+                .filter(|comparison| matches!(comparison.change, Change::Regression(_)))
+                .count()
+            {
+                0 => Ok(()),
+                regressions => Err(miette!(
+                    "{regressions} part(s) got more than {threshold}% slower"
+                )),
+            }
+        }
+        BenchCommand::Report { commit, output } => {
+            let run = find_run(&history, commit.as_deref())?;
+            std::fs::write(&output, bench::render(run))
+                .map_err(|error| miette!("could not write {}: {error}", output.display()))
+        }
+    }
+}
+
+#[tracing::instrument]
+fn main() -> miette::Result<()> {
+    tracing_subscriber::fmt::init();
+
+    match Cli::parse().command {
+        Command::Run {
+            day,
+            part,
+            input,
+            params,
+        } => run(day, part, input, params),
+        Command::Bench { command, history } => bench(command, &history),
+    }
+}
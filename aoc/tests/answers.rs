@@ -0,0 +1,120 @@
+//! Runs every registered solution against the known answers in `answers.txt`.
+
+use std::{
+    fmt::Write,
+    path::{Path, PathBuf},
+};
+
+use aoc::SOLUTIONS;
+
+#[derive(Debug)]
+struct Entry {
+    day: u8,
+    part: u8,
+    input: PathBuf,
+    answer: String,
+    slow: bool,
+}
+
+#[derive(Debug)]
+enum Outcome {
+    Pass(String),
+    Mismatch(String),
+    Failed(String),
+    Skipped(&'static str),
+}
+
+fn workspace_root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("..")
+}
+
+fn manifest() -> Vec<Entry> {
+    let manifest = std::fs::read_to_string(workspace_root().join("answers.txt"))
+        .expect("answers.txt should be at the workspace root");
+    manifest
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty() && !line.starts_with('#'))
+        .map(|(idx, line)| {
+            let fields = line.split_whitespace().collect::<Vec<&str>>();
+            match fields.as_slice() {
+                [day, part, input, answer, flags @ ..] => Entry {
+                    day: day.parse().expect("day should be a number"),
+                    part: part.parse().expect("part should be a number"),
+                    input: PathBuf::from(input),
+                    answer: answer.to_string(),
+                    slow: flags.contains(&"slow"),
+                },
+                _ => panic!("answers.txt:{}: expected `day part input answer`", idx + 1),
+            }
+        })
+        .collect()
+}
+
+fn check(entry: &Entry, run_slow: bool) -> Outcome {
+    let Some(solution) = SOLUTIONS
+        .iter()
+        .find(|solution| solution.day() == entry.day && solution.part() == entry.part)
+    else {
+        return Outcome::Failed("not registered".to_string());
+    };
+    if entry.slow && !run_slow {
+        return Outcome::Skipped("slow, set AOC_SLOW=1");
+    }
+    let Ok(input) = std::fs::read_to_string(workspace_root().join(&entry.input)) else {
+        return Outcome::Skipped("no input");
+    };
+    match solution.process(&input) {
+        Ok(answer) if answer == entry.answer => Outcome::Pass(answer),
+        Ok(answer) => Outcome::Mismatch(answer),
+        Err(error) => Outcome::Failed(error.to_string()),
+    }
+}
+
+#[test]
+fn test_known_answers() {
+    let run_slow = std::env::var_os("AOC_SLOW").is_some();
+    let entries = manifest();
+    let mut table = format!(
+        "{:<4} {:<4} {:<18} {:<18} status\n",
+        "day", "part", "expected", "actual"
+    );
+    let mut failures = 0;
+    for entry in &entries {
+        let (actual, status) = match check(entry, run_slow) {
+            Outcome::Pass(answer) => (answer, "ok"),
+            Outcome::Mismatch(answer) => {
+                failures += 1;
+                (answer, "MISMATCH")
+            }
+            Outcome::Failed(error) => {
+                failures += 1;
+                (error, "ERROR")
+            }
+            Outcome::Skipped(reason) => (String::new(), reason),
+        };
+        writeln!(
+            table,
+            "{:<4} {:<4} {:<18} {:<18} {status}",
+            entry.day, entry.part, entry.answer, actual
+        )
+        .unwrap();
+    }
+    for solution in SOLUTIONS.iter().filter(|solution| {
+        !entries
+            .iter()
+            .any(|entry| entry.day == solution.day() && entry.part == solution.part())
+    }) {
+        writeln!(
+            table,
+            "{:<4} {:<4} {:<18} {:<18} no known answer",
+            solution.day(),
+            solution.part(),
+            "",
+            ""
+        )
+        .unwrap();
+    }
+    println!("{table}");
+    assert_eq!(failures, 0, "{failures} answer(s) are wrong\n{table}");
+}
@@ -1,4 +1,4 @@
-use {{crate_name}}::part2::process;
+use {{crate_name}}::part2::{parse, solve};
 use miette::Context;
 
 #[cfg(feature = "dhat-heap")]
@@ -13,8 +13,12 @@ fn main() -> miette::Result<()> {
     #[cfg(not(feature = "dhat-heap"))]
     tracing_subscriber::fmt::init();
 
-    let file = include_str!("../../input2.txt");
-    let result = process(file).context("process part 2")?;
+    #[cfg(feature = "embedded-input")]
+    let file = include_str!("../../input2.txt").to_string();
+    #[cfg(not(feature = "embedded-input"))]
+    let file = aoc_core::input::from_args({{crate_name}}::DAY, 2)?;
+    let model = parse(&file).context("parse part 2")?;
+    let result = solve(&model).context("solve part 2")?;
     println!("{}", result);
     Ok(())
 }
@@ -2,3 +2,6 @@ pub mod custom_error;
 
 pub mod part1;
 pub mod part2;
+
+pub const DAY: u8 = {{project-name | remove: "day-" | plus: 0}};
+pub const TITLE: &str = "{{title}}";
@@ -1,12 +1,41 @@
+use aoc_core::Solution;
+
 use crate::custom_error::AocError;
 
 #[tracing::instrument]
-pub fn process(
-    _input: &str,
-) -> miette::Result<String, AocError> {
+pub fn parse(input: &str) -> miette::Result<Vec<&str>, AocError> {
+    Ok(input.lines().collect())
+}
+
+#[tracing::instrument(skip(_lines))]
+pub fn solve(_lines: &[&str]) -> miette::Result<String, AocError> {
     todo!("day 01 - part 1");
 }
 
+#[tracing::instrument]
+pub fn process(input: &str) -> miette::Result<String, AocError> {
+    solve(&parse(input)?)
+}
+
+pub struct Part1;
+
+impl Solution for Part1 {
+    const DAY: u8 = crate::DAY;
+    const PART: u8 = 1;
+    const TITLE: &'static str = crate::TITLE;
+
+    type Model<'a> = Vec<&'a str>;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Self::Model<'_>, AocError> {
+        parse(input)
+    }
+
+    fn solve(lines: &Self::Model<'_>, _params: &Self::Params) -> Result<String, AocError> {
+        solve(lines)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
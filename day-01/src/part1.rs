@@ -1,9 +1,31 @@
+use aoc_core::Solution;
+
 use crate::custom_error::AocError;
 
 #[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String, AocError> {
-    Ok(input
+pub fn parse(input: &str) -> miette::Result<Vec<&str>, AocError> {
+    input
         .lines()
+        .map(|line| {
+            if let Some((column, _)) = line.char_indices().find(|(_, ch)| !ch.is_ascii()) {
+                Err(AocError::parse_error(
+                    input,
+                    &line[column..],
+                    "expected an ascii character",
+                ))
+            } else if !line.chars().any(|ch| ch.is_ascii_digit()) {
+                Err(AocError::parse_error(input, line, "line has no digit"))
+            } else {
+                Ok(line)
+            }
+        })
+        .collect()
+}
+
+#[tracing::instrument(skip(lines))]
+pub fn solve(lines: &[&str]) -> miette::Result<String, AocError> {
+    Ok(lines
+        .iter()
         .fold(0_i32, |acc, x| {
             let res = x
                 .chars()
@@ -22,6 +44,30 @@ pub fn process(input: &str) -> miette::Result<String, AocError> {
         .to_string())
 }
 
+#[tracing::instrument]
+pub fn process(input: &str) -> miette::Result<String, AocError> {
+    solve(&parse(input)?)
+}
+
+pub struct Part1;
+
+impl Solution for Part1 {
+    const DAY: u8 = crate::DAY;
+    const PART: u8 = 1;
+    const TITLE: &'static str = crate::TITLE;
+
+    type Model<'a> = Vec<&'a str>;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Self::Model<'_>, AocError> {
+        parse(input)
+    }
+
+    fn solve(lines: &Self::Model<'_>, _params: &Self::Params) -> Result<String, AocError> {
+        solve(lines)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -35,4 +81,17 @@ treb7uchet";
         assert_eq!("142", process(input)?);
         Ok(())
     }
+
+    #[test]
+    fn test_parse_error() {
+        let error = parse("1abc2\npqrstu").unwrap_err();
+        assert!(matches!(
+            error,
+            AocError::ParseError {
+                line: 2,
+                column: 1,
+                ..
+            }
+        ));
+    }
 }
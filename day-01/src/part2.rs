@@ -1,16 +1,41 @@
+use aoc_core::Solution;
+
 use crate::custom_error::AocError;
 
+const NUM_STR: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
 #[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let num_str = [
-        "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
-    ];
-    Ok(input
+pub fn parse(input: &str) -> miette::Result<Vec<&str>, AocError> {
+    input
         .lines()
+        .map(|line| {
+            if let Some((column, _)) = line.char_indices().find(|(_, ch)| !ch.is_ascii()) {
+                Err(AocError::parse_error(
+                    input,
+                    &line[column..],
+                    "expected an ascii character",
+                ))
+            } else if !line.chars().any(|ch| ch.is_ascii_digit())
+                && !NUM_STR.iter().any(|num| line.contains(num))
+            {
+                Err(AocError::parse_error(input, line, "line has no digit"))
+            } else {
+                Ok(line)
+            }
+        })
+        .collect()
+}
+
+#[tracing::instrument(skip(lines))]
+pub fn solve(lines: &[&str]) -> miette::Result<String, AocError> {
+    Ok(lines
+        .iter()
         .fold(0_i32, |acc, x| {
             let (mut l_dg, mut r_dg) = (0_u8, 0_u8);
             for i in 0..x.len() {
-                for (idx, s) in num_str.iter().enumerate() {
+                for (idx, s) in NUM_STR.iter().enumerate() {
                     if x[i..].starts_with(s) {
                         if l_dg != 0 {
                             r_dg = (idx + 1) as u8;
@@ -33,6 +58,30 @@ pub fn process(input: &str) -> miette::Result<String, AocError> {
         .to_string())
 }
 
+#[tracing::instrument]
+pub fn process(input: &str) -> miette::Result<String, AocError> {
+    solve(&parse(input)?)
+}
+
+pub struct Part2;
+
+impl Solution for Part2 {
+    const DAY: u8 = crate::DAY;
+    const PART: u8 = 2;
+    const TITLE: &'static str = crate::TITLE;
+
+    type Model<'a> = Vec<&'a str>;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Self::Model<'_>, AocError> {
+        parse(input)
+    }
+
+    fn solve(lines: &Self::Model<'_>, _params: &Self::Params) -> Result<String, AocError> {
+        solve(lines)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -49,4 +98,17 @@ zoneight234
         assert_eq!("281", process(input)?);
         Ok(())
     }
+
+    #[test]
+    fn test_parse_error() {
+        let error = parse("1abc2\npqrstu").unwrap_err();
+        assert!(matches!(
+            error,
+            AocError::ParseError {
+                line: 2,
+                column: 1,
+                ..
+            }
+        ));
+    }
 }
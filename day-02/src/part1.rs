@@ -1,58 +1,108 @@
+use aoc_core::{parse::finish, Solution};
+
 use crate::custom_error::AocError;
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{self, line_ending},
+    multi::separated_list1,
+    sequence::{preceded, separated_pair, tuple},
+    IResult,
+};
+
+#[derive(Debug, Default)]
+pub struct Cubes {
+    pub red: u32,
+    pub green: u32,
+    pub blue: u32,
+}
+
+#[derive(Debug)]
+pub struct Game {
+    pub id: u32,
+    pub sets: Vec<Cubes>,
+}
+
+fn cubes(input: &str) -> IResult<&str, Cubes> {
+    let (input, counts) = separated_list1(
+        tag(", "),
+        separated_pair(
+            complete::u32,
+            tag(" "),
+            alt((tag("red"), tag("green"), tag("blue"))),
+        ),
+    )(input)?;
+    let mut cubes = Cubes::default();
+    for (count, color) in counts {
+        match color {
+            "red" => cubes.red = count,
+            "green" => cubes.green = count,
+            _ => cubes.blue = count,
+        }
+    }
+    Ok((input, cubes))
+}
+
+fn game(input: &str) -> IResult<&str, Game> {
+    let (input, (id, sets)) = tuple((
+        preceded(tag("Game "), complete::u32),
+        preceded(tag(": "), separated_list1(tag("; "), cubes)),
+    ))(input)?;
+    Ok((input, Game { id, sets }))
+}
+
+fn parse_input(input: &str) -> IResult<&str, Vec<Game>> {
+    separated_list1(line_ending, game)(input)
+}
 
 #[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let colors = vec![("red", 12), ("green", 13), ("blue", 14)];
-    let mut successfull = true;
-    let mut start_index = 0;
-    Ok(input
-        .lines()
-        .enumerate()
-        .fold(0_i32, |acc, (game, x)| {
-            successfull = true;
-            let games = x[8 + (game + 1).ilog10() as usize..]
-                .split("; ")
-                .collect::<Vec<&str>>();
-            'outer: for game in games {
-                start_index = 0;
-                while let Some(end_index) = game[start_index..].find(',') {
-                    for (color, max_num) in &colors {
-                        if game[start_index..start_index + end_index].ends_with(color) {
-                            if let Ok(num) = game
-                                [start_index..start_index + end_index - color.len() - 1]
-                                .parse::<i32>()
-                            {
-                                if num > *max_num {
-                                    successfull = false;
-                                    break 'outer;
-                                }
-                            }
-                        }
-                    }
-                    start_index += end_index + 2;
-                }
-                for (color, max_num) in &colors {
-                    if game[start_index..].ends_with(color) {
-                        if let Ok(num) =
-                            game[start_index..game.len() - color.len() - 1].parse::<i32>()
-                        {
-                            if num > *max_num {
-                                successfull = false;
-                                break 'outer;
-                            }
-                        }
-                    }
-                }
-            }
-            start_index = 0;
-            match successfull {
-                true => acc + game as i32 + 1,
-                false => acc,
-            }
+pub fn parse(input: &str) -> miette::Result<Vec<Game>, AocError> {
+    finish(input, parse_input)
+}
+
+#[tracing::instrument(skip(games))]
+pub fn solve(games: &[Game]) -> miette::Result<String, AocError> {
+    let bag = Cubes {
+        red: 12,
+        green: 13,
+        blue: 14,
+    };
+    Ok(games
+        .iter()
+        .filter(|game| {
+            game.sets
+                .iter()
+                .all(|set| set.red <= bag.red && set.green <= bag.green && set.blue <= bag.blue)
         })
+        .map(|game| u64::from(game.id))
+        .sum::<u64>()
         .to_string())
 }
 
+#[tracing::instrument]
+pub fn process(input: &str) -> miette::Result<String, AocError> {
+    solve(&parse(input)?)
+}
+
+pub struct Part1;
+
+impl Solution for Part1 {
+    const DAY: u8 = crate::DAY;
+    const PART: u8 = 1;
+    const TITLE: &'static str = crate::TITLE;
+
+    type Model<'a> = Vec<Game>;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Self::Model<'_>, AocError> {
+        parse(input)
+    }
+
+    fn solve(games: &Self::Model<'_>, _params: &Self::Params) -> Result<String, AocError> {
+        solve(games)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -67,4 +117,18 @@ Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
         assert_eq!("8", process(input)?);
         Ok(())
     }
+
+    #[test]
+    fn test_parse_error() {
+        let input = "Game 1: 3 blue, 4 red
+Game 2: 1 blue, 2 purple";
+        assert!(matches!(
+            parse(input),
+            Err(AocError::ParseError {
+                line: 2,
+                column: 15,
+                ..
+            })
+        ));
+    }
 }
@@ -1,59 +1,108 @@
+use aoc_core::{parse::finish, Solution};
+
 use crate::custom_error::AocError;
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{self, line_ending},
+    multi::separated_list1,
+    sequence::{preceded, separated_pair, tuple},
+    IResult,
+};
+
+#[derive(Debug, Default)]
+pub struct Cubes {
+    pub red: u32,
+    pub green: u32,
+    pub blue: u32,
+}
 
-struct Color<'a> {
-    name: &'a str,
-    max: i32,
+#[derive(Debug)]
+pub struct Game {
+    pub id: u32,
+    pub sets: Vec<Cubes>,
 }
 
-impl<'a> Color<'a> {
-    fn new(name: &'a str) -> Self {
-        Self { name, max: 0 }
+fn cubes(input: &str) -> IResult<&str, Cubes> {
+    let (input, counts) = separated_list1(
+        tag(", "),
+        separated_pair(
+            complete::u32,
+            tag(" "),
+            alt((tag("red"), tag("green"), tag("blue"))),
+        ),
+    )(input)?;
+    let mut cubes = Cubes::default();
+    for (count, color) in counts {
+        match color {
+            "red" => cubes.red = count,
+            "green" => cubes.green = count,
+            _ => cubes.blue = count,
+        }
     }
+    Ok((input, cubes))
+}
+
+fn game(input: &str) -> IResult<&str, Game> {
+    let (input, (id, sets)) = tuple((
+        preceded(tag("Game "), complete::u32),
+        preceded(tag(": "), separated_list1(tag("; "), cubes)),
+    ))(input)?;
+    Ok((input, Game { id, sets }))
+}
+
+fn parse_input(input: &str) -> IResult<&str, Vec<Game>> {
+    separated_list1(line_ending, game)(input)
+}
+
+#[tracing::instrument]
+pub fn parse(input: &str) -> miette::Result<Vec<Game>, AocError> {
+    finish(input, parse_input)
+}
+
+#[tracing::instrument(skip(games))]
+pub fn solve(games: &[Game]) -> miette::Result<String, AocError> {
+    let mut res = 0_u64;
+    for game in games {
+        let fewest = game
+            .sets
+            .iter()
+            .fold(Cubes::default(), |fewest, set| Cubes {
+                red: fewest.red.max(set.red),
+                green: fewest.green.max(set.green),
+                blue: fewest.blue.max(set.blue),
+            });
+        res = u64::from(fewest.red)
+            .checked_mul(u64::from(fewest.green))
+            .and_then(|power| power.checked_mul(u64::from(fewest.blue)))
+            .and_then(|power| res.checked_add(power))
+            .ok_or_else(|| AocError::invalid_input("sum of the powers overflows"))?;
+    }
+    Ok(res.to_string())
 }
 
 #[tracing::instrument]
 pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let mut colors = vec![Color::new("red"), Color::new("green"), Color::new("blue")];
-    let mut successfull = true;
-    let mut start_index = 0;
-    Ok(input
-        .lines()
-        .enumerate()
-        .fold(0_i32, |acc, (game, x)| {
-            (colors[0].max, colors[1].max, colors[2].max) = (0, 0, 0);
-            successfull = true;
-            let games = x[8 + (game + 1).ilog10() as usize..]
-                .split("; ")
-                .collect::<Vec<&str>>();
-            for game in games {
-                start_index = 0;
-                while let Some(end_index) = game[start_index..].find(',') {
-                    for color in colors.iter_mut() {
-                        if game[start_index..start_index + end_index].ends_with(color.name) {
-                            if let Ok(num) = game
-                                [start_index..start_index + end_index - color.name.len() - 1]
-                                .parse::<i32>()
-                            {
-                                color.max = num.max(color.max)
-                            }
-                        }
-                    }
-                    start_index += end_index + 2;
-                }
-                for color in colors.iter_mut() {
-                    if game[start_index..].ends_with(color.name) {
-                        if let Ok(num) =
-                            game[start_index..game.len() - color.name.len() - 1].parse::<i32>()
-                        {
-                            color.max = num.max(color.max)
-                        }
-                    }
-                }
-            }
-            start_index = 0;
-            acc + colors[0].max * colors[1].max * colors[2].max
-        })
-        .to_string())
+    solve(&parse(input)?)
+}
+
+pub struct Part2;
+
+impl Solution for Part2 {
+    const DAY: u8 = crate::DAY;
+    const PART: u8 = 2;
+    const TITLE: &'static str = crate::TITLE;
+
+    type Model<'a> = Vec<Game>;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Self::Model<'_>, AocError> {
+        parse(input)
+    }
+
+    fn solve(games: &Self::Model<'_>, _params: &Self::Params) -> Result<String, AocError> {
+        solve(games)
+    }
 }
 
 #[cfg(test)]
@@ -1,5 +1,7 @@
 use nom::AsChar;
 
+use aoc_core::{parse::grid, Solution};
+
 use crate::custom_error::AocError;
 
 macro_rules! is_special {
@@ -9,17 +11,21 @@ macro_rules! is_special {
 }
 
 #[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let mut res = 0;
-    let to_vec = input.lines().map(|a| a.as_bytes()).collect::<Vec<&[u8]>>();
+pub fn parse(input: &str) -> miette::Result<Vec<&[u8]>, AocError> {
+    Ok(grid(input)?.into_iter().map(|a| a.as_bytes()).collect())
+}
+
+#[tracing::instrument(skip(to_vec))]
+pub fn solve(to_vec: &[&[u8]]) -> miette::Result<String, AocError> {
+    let mut res = 0_u64;
     let mut in_num = false;
     let mut start_index = 0;
     let line_len = to_vec[0].len();
-    for i in 0..line_len {
+    for i in 0..to_vec.len() {
         let mut special_index = i32::MIN;
         for j in 0..line_len {
             if (i > 0 && is_special!(to_vec[i - 1][j]))
-                || (i < line_len - 1 && is_special!(to_vec[i + 1][j]))
+                || (i + 1 < to_vec.len() && is_special!(to_vec[i + 1][j]))
                 || is_special!(to_vec[i][j])
             {
                 special_index = j as i32;
@@ -30,15 +36,16 @@ pub fn process(input: &str) -> miette::Result<String, AocError> {
             }
             if in_num && (!to_vec[i][j].is_dec_digit() || j + 1 == line_len) {
                 if special_index >= start_index as i32 - 1 {
-                    if !to_vec[i][j].is_dec_digit() {
-                        res += String::from_utf8_lossy(&to_vec[i][start_index..j])
-                            .parse::<i32>()
-                            .expect("Valid number");
-                    } else if j + 1 == line_len {
-                        res += String::from_utf8_lossy(&to_vec[i][start_index..])
-                            .parse::<i32>()
-                            .expect("Valid number");
-                    }
+                    let digits = match to_vec[i][j].is_dec_digit() {
+                        true => &to_vec[i][start_index..],
+                        false => &to_vec[i][start_index..j],
+                    };
+                    let part_number = String::from_utf8_lossy(digits)
+                        .parse::<u64>()
+                        .map_err(|_| AocError::invalid_input("part number overflows"))?;
+                    res = res.checked_add(part_number).ok_or_else(|| {
+                        AocError::invalid_input("sum of the part numbers overflows")
+                    })?;
                 }
                 in_num = false;
             }
@@ -47,6 +54,30 @@ pub fn process(input: &str) -> miette::Result<String, AocError> {
     Ok(res.to_string())
 }
 
+#[tracing::instrument]
+pub fn process(input: &str) -> miette::Result<String, AocError> {
+    solve(&parse(input)?)
+}
+
+pub struct Part1;
+
+impl Solution for Part1 {
+    const DAY: u8 = crate::DAY;
+    const PART: u8 = 1;
+    const TITLE: &'static str = crate::TITLE;
+
+    type Model<'a> = Vec<&'a [u8]>;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Self::Model<'_>, AocError> {
+        parse(input)
+    }
+
+    fn solve(schematic: &Self::Model<'_>, _params: &Self::Params) -> Result<String, AocError> {
+        solve(schematic)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -66,4 +97,19 @@ mod tests {
         assert_eq!("4361", process(input)?);
         Ok(())
     }
+
+    #[test]
+    fn test_parse_error() {
+        let input = "467..114..
+...*.....
+..35..633.";
+        assert!(matches!(
+            parse(input),
+            Err(AocError::ParseError {
+                line: 2,
+                column: 1,
+                ..
+            })
+        ));
+    }
 }
@@ -2,16 +2,18 @@ use std::collections::HashSet;
 
 use nom::AsChar;
 
+use aoc_core::{parse::grid, Solution};
+
 use crate::custom_error::AocError;
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
-enum Entry {
+pub enum Entry {
     Default,
     Number(u32),
     Star,
 }
 
-fn neighbours_product((y, x): &(usize, usize), map: &Vec<Vec<Entry>>) -> u32 {
+fn neighbours_product((y, x): &(usize, usize), map: &[Vec<Entry>]) -> u64 {
     let mut neighbours = HashSet::new();
     if *y > 0 {
         if let Entry::Number(num) = map[*y - 1][*x] {
@@ -54,20 +56,26 @@ fn neighbours_product((y, x): &(usize, usize), map: &Vec<Vec<Entry>>) -> u32 {
         }
     }
     if neighbours.len() == 2 {
-        neighbours.into_iter().fold(1, |acc, x| acc * x)
+        neighbours.into_iter().map(u64::from).product()
     } else {
         0
     }
 }
 
+fn number(input: &str, digits: &str) -> Result<u32, AocError> {
+    digits
+        .parse::<u32>()
+        .map_err(|_| AocError::parse_error(input, digits, "number does not fit in a u32"))
+}
+
 #[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let lines = input.lines().collect::<Vec<&str>>();
+pub fn parse(input: &str) -> miette::Result<Vec<Vec<Entry>>, AocError> {
+    let lines = grid(input)?;
     let mut map = vec![vec![Entry::Default; lines[0].len()]; lines.len()];
     for (y, line) in lines.into_iter().enumerate() {
         let mut num_starting_point = 0;
         let mut in_num = false;
-        for (x, ch) in line.chars().enumerate() {
+        for (x, ch) in line.char_indices() {
             if ch == '*' {
                 map[y][x] = Entry::Star;
             }
@@ -77,40 +85,63 @@ pub fn process(input: &str) -> miette::Result<String, AocError> {
                     in_num = true;
                 }
                 if x + 1 == line.len() && in_num {
-                    for i in num_starting_point..x + 1 {
-                        map[y][i] = Entry::Number(
-                            line[num_starting_point..x + 1]
-                                .parse::<u32>()
-                                .expect("Should be valid"),
-                        );
-                    }
+                    map[y][num_starting_point..x + 1].fill(Entry::Number(number(
+                        input,
+                        &line[num_starting_point..x + 1],
+                    )?));
                 }
             }
             if !ch.is_dec_digit() {
                 if in_num {
-                    for i in num_starting_point..x {
-                        map[y][i] = Entry::Number(
-                            line[num_starting_point..x]
-                                .parse::<u32>()
-                                .expect("Should be valid"),
-                        );
-                    }
+                    map[y][num_starting_point..x]
+                        .fill(Entry::Number(number(input, &line[num_starting_point..x])?));
                 }
                 in_num = false;
             }
         }
     }
-    let mut res = 0_u32;
+    Ok(map)
+}
+
+#[tracing::instrument(skip(map))]
+pub fn solve(map: &[Vec<Entry>]) -> miette::Result<String, AocError> {
+    let mut res = 0_u64;
     for (y, line) in map.iter().enumerate() {
         for (x, entry) in line.iter().enumerate() {
             if *entry == Entry::Star {
-                res += neighbours_product(&(y, x), &map);
+                res = res
+                    .checked_add(neighbours_product(&(y, x), map))
+                    .ok_or_else(|| AocError::invalid_input("sum of the gear ratios overflows"))?;
             }
         }
     }
     Ok(res.to_string())
 }
 
+#[tracing::instrument]
+pub fn process(input: &str) -> miette::Result<String, AocError> {
+    solve(&parse(input)?)
+}
+
+pub struct Part2;
+
+impl Solution for Part2 {
+    const DAY: u8 = crate::DAY;
+    const PART: u8 = 2;
+    const TITLE: &'static str = crate::TITLE;
+
+    type Model<'a> = Vec<Vec<Entry>>;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Self::Model<'_>, AocError> {
+        parse(input)
+    }
+
+    fn solve(map: &Self::Model<'_>, _params: &Self::Params) -> Result<String, AocError> {
+        solve(map)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,4 +1,4 @@
-use day_04::part1::process;
+use day_04::part1::{parse, solve};
 use miette::Context;
 
 #[cfg(feature = "dhat-heap")]
@@ -13,8 +13,12 @@ fn main() -> miette::Result<()> {
     #[cfg(not(feature = "dhat-heap"))]
     tracing_subscriber::fmt::init();
 
-    let file = include_str!("../../input1.txt");
-    let result = process(file).context("process part 1")?;
+    #[cfg(feature = "embedded-input")]
+    let file = include_str!("../../input1.txt").to_string();
+    #[cfg(not(feature = "embedded-input"))]
+    let file = aoc_core::input::from_args(day_04::DAY, 1)?;
+    let model = parse(&file).context("parse part 1")?;
+    let result = solve(&model).context("solve part 1")?;
     println!("{}", result);
     Ok(())
 }
@@ -1,47 +1,96 @@
 use std::collections::HashSet;
 
+use aoc_core::{parse::finish, Solution};
+
 use crate::custom_error::AocError;
+use nom::{
+    bytes::complete::tag,
+    character::complete::{self, line_ending, space1},
+    multi::separated_list1,
+    sequence::{delimited, preceded, terminated, tuple},
+    IResult,
+};
+
+#[derive(Debug)]
+pub struct Card {
+    pub id: u32,
+    pub winning: HashSet<u32>,
+    pub numbers: Vec<u32>,
+}
+
+impl Card {
+    pub fn matches(&self) -> u32 {
+        self.numbers
+            .iter()
+            .filter(|number| self.winning.contains(number))
+            .count() as u32
+    }
+}
+
+fn numbers(input: &str) -> IResult<&str, Vec<u32>> {
+    preceded(space1, separated_list1(space1, complete::u32))(input)
+}
+
+fn card(input: &str) -> IResult<&str, Card> {
+    let (input, (id, winning, numbers)) = tuple((
+        delimited(tuple((tag("Card"), space1)), complete::u32, tag(":")),
+        terminated(numbers, tuple((space1, tag("|")))),
+        numbers,
+    ))(input)?;
+    Ok((
+        input,
+        Card {
+            id,
+            winning: winning.into_iter().collect(),
+            numbers,
+        },
+    ))
+}
+
+fn parse_input(input: &str) -> IResult<&str, Vec<Card>> {
+    separated_list1(line_ending, card)(input)
+}
+
+#[tracing::instrument]
+pub fn parse(input: &str) -> miette::Result<Vec<Card>, AocError> {
+    finish(input, parse_input)
+}
+
+#[tracing::instrument(skip(cards))]
+pub fn solve(cards: &[Card]) -> miette::Result<String, AocError> {
+    let points = cards
+        .iter()
+        .try_fold(0_u64, |acc, card| match card.matches() {
+            0 => Some(acc),
+            any => acc.checked_add(1_u64.checked_shl(any - 1)?),
+        });
+    points
+        .map(|points| points.to_string())
+        .ok_or_else(|| AocError::invalid_input("the points do not fit in a u64"))
+}
 
 #[tracing::instrument]
 pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let dataset_start_point = 6 + input.len().ilog10() as usize;
-    Ok(input
-        .lines()
-        .fold(0_u32, |acc, line| {
-            match line[dataset_start_point..]
-                .split(' ')
-                .filter(|a| !a.is_empty())
-                .fold(
-                    (0_u32, HashSet::new(), false),
-                    |(found, mut my_hashset, passed_pipe), v| {
-                        if v == "|" {
-                            (found, my_hashset, true)
-                        } else {
-                            match passed_pipe {
-                                true => {
-                                    if my_hashset
-                                        .contains(&v.parse::<u32>().expect("Should be valid"))
-                                    {
-                                        (found + 1, my_hashset, passed_pipe)
-                                    } else {
-                                        (found, my_hashset, passed_pipe)
-                                    }
-                                }
-                                false => {
-                                    my_hashset.insert(v.parse::<u32>().expect("Should be valid"));
-                                    (found, my_hashset, passed_pipe)
-                                }
-                            }
-                        }
-                    },
-                )
-                .0
-            {
-                0 => acc,
-                any => acc + 2_u32.pow(any - 1),
-            }
-        })
-        .to_string())
+    solve(&parse(input)?)
+}
+
+pub struct Part1;
+
+impl Solution for Part1 {
+    const DAY: u8 = crate::DAY;
+    const PART: u8 = 1;
+    const TITLE: &'static str = crate::TITLE;
+
+    type Model<'a> = Vec<Card>;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Self::Model<'_>, AocError> {
+        parse(input)
+    }
+
+    fn solve(cards: &Self::Model<'_>, _params: &Self::Params) -> Result<String, AocError> {
+        solve(cards)
+    }
 }
 
 #[cfg(test)]
@@ -1,61 +1,101 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::HashSet;
+
+use aoc_core::{parse::finish, Solution};
 
 use crate::custom_error::AocError;
+use nom::{
+    bytes::complete::tag,
+    character::complete::{self, line_ending, space1},
+    multi::separated_list1,
+    sequence::{delimited, preceded, terminated, tuple},
+    IResult,
+};
+
+#[derive(Debug)]
+pub struct Card {
+    pub id: u32,
+    pub winning: HashSet<u32>,
+    pub numbers: Vec<u32>,
+}
+
+impl Card {
+    pub fn matches(&self) -> u32 {
+        self.numbers
+            .iter()
+            .filter(|number| self.winning.contains(number))
+            .count() as u32
+    }
+}
+
+fn numbers(input: &str) -> IResult<&str, Vec<u32>> {
+    preceded(space1, separated_list1(space1, complete::u32))(input)
+}
+
+fn card(input: &str) -> IResult<&str, Card> {
+    let (input, (id, winning, numbers)) = tuple((
+        delimited(tuple((tag("Card"), space1)), complete::u32, tag(":")),
+        terminated(numbers, tuple((space1, tag("|")))),
+        numbers,
+    ))(input)?;
+    Ok((
+        input,
+        Card {
+            id,
+            winning: winning.into_iter().collect(),
+            numbers,
+        },
+    ))
+}
+
+fn parse_input(input: &str) -> IResult<&str, Vec<Card>> {
+    separated_list1(line_ending, card)(input)
+}
+
+#[tracing::instrument]
+pub fn parse(input: &str) -> miette::Result<Vec<Card>, AocError> {
+    finish(input, parse_input)
+}
+
+#[tracing::instrument(skip(cards))]
+pub fn solve(cards: &[Card]) -> miette::Result<String, AocError> {
+    let overflow = || AocError::invalid_input("the number of scratchcards does not fit in a u64");
+    let mut copies = vec![1_u64; cards.len()];
+    for (idx, card) in cards.iter().enumerate() {
+        let card_num_entries = copies[idx];
+        let won = idx + 1..(idx + 1 + card.matches() as usize).min(cards.len());
+        for copy in &mut copies[won] {
+            *copy = copy.checked_add(card_num_entries).ok_or_else(overflow)?;
+        }
+    }
+    copies
+        .iter()
+        .try_fold(0_u64, |total, copies| total.checked_add(*copies))
+        .map(|total| total.to_string())
+        .ok_or_else(overflow)
+}
 
 #[tracing::instrument]
 pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let dataset_start_point = 6 + input.len().ilog10() as usize;
-    Ok(input
-        .lines()
-        .enumerate()
-        .fold(
-            (0_u32, HashMap::new()),
-            |(acc, mut my_hashmap), (idx, line)| {
-                my_hashmap
-                    .entry(idx + 1)
-                    .and_modify(|k| *k += 1)
-                    .or_insert(1);
-                let matched = line[dataset_start_point..]
-                    .split(' ')
-                    .filter(|a| !a.is_empty())
-                    .fold(
-                        (0_u32, HashSet::new(), false),
-                        |(found, mut my_hashset, passed_pipe), v| {
-                            if v == "|" {
-                                (found, my_hashset, true)
-                            } else {
-                                match passed_pipe {
-                                    true => {
-                                        if my_hashset
-                                            .contains(&v.parse::<u32>().expect("Should be valid"))
-                                        {
-                                            (found + 1, my_hashset, passed_pipe)
-                                        } else {
-                                            (found, my_hashset, passed_pipe)
-                                        }
-                                    }
-                                    false => {
-                                        my_hashset
-                                            .insert(v.parse::<u32>().expect("Should be valid"));
-                                        (found, my_hashset, passed_pipe)
-                                    }
-                                }
-                            }
-                        },
-                    )
-                    .0;
-                let card_num_entries = &my_hashmap.remove(&(idx + 1)).expect("Should exist");
-                (idx + 2..idx + 2 + matched as usize).for_each(|v| {
-                    my_hashmap
-                        .entry(v)
-                        .and_modify(|k| *k += *card_num_entries)
-                        .or_insert(*card_num_entries);
-                });
-                (acc + card_num_entries, my_hashmap)
-            },
-        )
-        .0
-        .to_string())
+    solve(&parse(input)?)
+}
+
+pub struct Part2;
+
+impl Solution for Part2 {
+    const DAY: u8 = crate::DAY;
+    const PART: u8 = 2;
+    const TITLE: &'static str = crate::TITLE;
+
+    type Model<'a> = Vec<Card>;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Self::Model<'_>, AocError> {
+        parse(input)
+    }
+
+    fn solve(cards: &Self::Model<'_>, _params: &Self::Params) -> Result<String, AocError> {
+        solve(cards)
+    }
 }
 
 #[cfg(test)]
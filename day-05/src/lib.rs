@@ -2,3 +2,7 @@ pub mod custom_error;
 
 pub mod part1;
 pub mod part2;
+pub mod seed_map;
+
+pub const DAY: u8 = 5;
+pub const TITLE: &str = "If You Give A Seed A Fertilizer";
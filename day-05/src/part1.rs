@@ -1,57 +1,13 @@
+use aoc_core::{parse::finish, Solution};
+
 use crate::custom_error::AocError;
+use crate::seed_map::{seed_map, SeedMap};
 use nom::{
-    bytes::complete::take_until,
-    character::complete::{self, line_ending, space1},
+    character::complete::{self, space1},
     multi::{many1, separated_list1},
-    sequence::tuple,
     IResult, Parser,
 };
 use nom_supreme::{tag::complete::tag, ParserExt};
-use std::ops::Range;
-
-#[derive(Debug)]
-struct SeedMap {
-    mappings: Vec<(Range<u64>, Range<u64>)>,
-}
-
-impl SeedMap {
-    fn get_map(&self, source: u64) -> u64 {
-        let valid_mapping = self
-            .mappings
-            .iter()
-            .find(|(source_map, _)| source_map.contains(&source));
-        match valid_mapping {
-            Some((source_map, destination_map)) => {
-                destination_map.start + (source - source_map.start)
-            }
-            None => source,
-        }
-    }
-}
-
-fn create_map(input: &str) -> IResult<&str, (Range<u64>, Range<u64>)> {
-    let (input, (destination, source, range)) = tuple((
-        complete::u64,
-        complete::u64.preceded_by(tag(" ")),
-        complete::u64.preceded_by(tag(" ")),
-    ))(input)?;
-
-    Ok((
-        input,
-        (
-            (source..source + range),
-            (destination..source + destination),
-        ),
-    ))
-}
-
-#[tracing::instrument]
-fn seed_map(input: &str) -> IResult<&str, SeedMap> {
-    take_until("map:")
-        .precedes(tag("map:"))
-        .precedes(many1(line_ending.precedes(create_map)).map(|mappings| SeedMap { mappings }))
-        .parse(input)
-}
 
 #[tracing::instrument]
 fn parse_seeds(input: &str) -> IResult<&str, (Vec<SeedMap>, Vec<u64>)> {
@@ -61,21 +17,61 @@ fn parse_seeds(input: &str) -> IResult<&str, (Vec<SeedMap>, Vec<u64>)> {
     let (input, maps) = many1(seed_map)(input)?;
     Ok((input, (maps, seeds)))
 }
+
+#[derive(Debug)]
+pub struct Almanac {
+    pub seeds: Vec<u64>,
+    pub maps: Vec<SeedMap>,
+}
+
 #[tracing::instrument(skip(input), fields(input_first_line = input.split('\n').next().unwrap()))]
-pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let (_, (seedmaps, seeds)) = parse_seeds(input).expect("Should be valid");
+pub fn parse(input: &str) -> miette::Result<Almanac, AocError> {
+    let (maps, seeds) = finish(input, parse_seeds)?;
+    Ok(Almanac { seeds, maps })
+}
+
+#[tracing::instrument(skip(almanac))]
+pub fn solve(almanac: &Almanac) -> miette::Result<String, AocError> {
+    let Almanac {
+        seeds,
+        maps: seedmaps,
+    } = almanac;
     let min_loc = seeds
         .iter()
         .map(|seed| {
             seedmaps
                 .iter()
-                .fold(*seed, |seed, seed_map| seed_map.get_map(seed))
+                .fold(*seed, |seed, seed_map| seed_map.get(seed))
         })
         .min()
-        .expect("Should exist");
+        .ok_or_else(|| AocError::invalid_input("the almanac has no seeds"))?;
     Ok(min_loc.to_string())
 }
 
+#[tracing::instrument(skip(input), fields(input_first_line = input.split('\n').next().unwrap()))]
+pub fn process(input: &str) -> miette::Result<String, AocError> {
+    solve(&parse(input)?)
+}
+
+pub struct Part1;
+
+impl Solution for Part1 {
+    const DAY: u8 = crate::DAY;
+    const PART: u8 = 1;
+    const TITLE: &'static str = crate::TITLE;
+
+    type Model<'a> = Almanac;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Self::Model<'_>, AocError> {
+        parse(input)
+    }
+
+    fn solve(almanac: &Self::Model<'_>, _params: &Self::Params) -> Result<String, AocError> {
+        solve(almanac)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,418 @@
+use std::ops::Range;
+
+use nom::{
+    bytes::complete::take_until,
+    character::complete::{self, line_ending},
+    error::{Error, ErrorKind},
+    multi::many1,
+    sequence::tuple,
+    IResult, Parser,
+};
+use nom_supreme::{tag::complete::tag, ParserExt};
+
+use crate::custom_error::AocError;
+
+/// One step of the almanac, e.g. seed-to-soil. The numbers of a source range move
+/// by the same offset into their destination, every other number keeps its value.
+///
+/// The mappings are kept sorted, disjoint and merged where they continue each
+/// other, so two maps are equal exactly when they map every number the same way.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SeedMap {
+    /// Source ranges and the number their start maps to
+    mappings: Vec<(Range<u64>, u64)>,
+}
+
+impl SeedMap {
+    /// Builds the map from source ranges and the start of their destinations.
+    /// Where sources overlap the one starting first wins. Fails when a
+    /// destination range does not fit in a `u64`.
+    pub fn new(mappings: impl IntoIterator<Item = (Range<u64>, u64)>) -> Result<Self, AocError> {
+        let mappings = mappings
+            .into_iter()
+            .map(
+                |(source, destination)| match destination_end(&source, destination) {
+                    Some(_) => Ok((source, destination)),
+                    None => Err(AocError::invalid_input(format!(
+                    "the destination of {source:?} starting at {destination} does not fit in a u64"
+                ))),
+                },
+            )
+            .collect::<Result<_, _>>()?;
+        Ok(SeedMap::normalized(mappings))
+    }
+
+    pub fn get(&self, source: u64) -> u64 {
+        let idx = self
+            .mappings
+            .partition_point(|(source_map, _)| source_map.start <= source);
+        match idx.checked_sub(1).map(|idx| &self.mappings[idx]) {
+            Some((source_map, destination)) if source_map.contains(&source) => {
+                destination + (source - source_map.start)
+            }
+            _ => source,
+        }
+    }
+
+    /// The pieces the map is linear on as `(source, destination)` pairs, in order
+    /// and covering `0..u64::MAX`, `u64::MAX` itself always keeps its value. The
+    /// starts of the sources are the breakpoints of the map.
+    pub fn segments(&self) -> Vec<(Range<u64>, Range<u64>)> {
+        self.split(0..u64::MAX)
+            .into_iter()
+            .map(|(source, destination)| {
+                let end = destination + (source.end - source.start);
+                (source, destination..end)
+            })
+            .collect()
+    }
+
+    /// The map that applies `self` and then `then`, i.e.
+    /// `self.compose(then).get(x) == then.get(self.get(x))`.
+    pub fn compose(&self, then: &SeedMap) -> SeedMap {
+        let mut mappings = Vec::new();
+        for (source, destination) in self.split(0..u64::MAX) {
+            let len = source.end - source.start;
+            for (image, target) in then.split(destination..destination + len) {
+                let start = source.start + (image.start - destination);
+                mappings.push((start..start + (image.end - image.start), target));
+            }
+        }
+        SeedMap::normalized(mappings)
+    }
+
+    /// The map from every destination back to its source, `None` when two numbers
+    /// map to the same one.
+    pub fn invert(&self) -> Option<SeedMap> {
+        let mut destinations = self
+            .mappings
+            .iter()
+            .map(|(source, destination)| *destination..destination + (source.end - source.start))
+            .collect::<Vec<Range<u64>>>();
+        destinations.sort_unstable_by_key(|destination| destination.start);
+        if destinations
+            .windows(2)
+            .any(|pair| pair[0].end > pair[1].start)
+        {
+            return None;
+        }
+        // The numbers outside of every source keep their value, so nothing else
+        // may be mapped onto them
+        let sources = self.mappings.iter().map(|(source, _)| source.clone());
+        if RangeMap::new(destinations) != RangeMap::new(sources) {
+            return None;
+        }
+        Some(SeedMap::normalized(
+            self.mappings
+                .iter()
+                .map(|(source, destination)| {
+                    (
+                        *destination..destination + (source.end - source.start),
+                        source.start,
+                    )
+                })
+                .collect(),
+        ))
+    }
+
+    /// Splits `range` where the mappings start and end, pairing every piece with
+    /// the number its start maps to.
+    fn split(&self, range: Range<u64>) -> Vec<(Range<u64>, u64)> {
+        let mut pieces = Vec::new();
+        if range.is_empty() {
+            return pieces;
+        }
+        let mut start = range.start;
+        let first = self
+            .mappings
+            .partition_point(|(source_map, _)| source_map.end <= start);
+        for (source_map, destination) in &self.mappings[first..] {
+            if source_map.start >= range.end {
+                break;
+            }
+            if source_map.start > start {
+                pieces.push((start..source_map.start, start));
+                start = source_map.start;
+            }
+            let end = range.end.min(source_map.end);
+            pieces.push((start..end, destination + (start - source_map.start)));
+            start = end;
+        }
+        if start < range.end {
+            pieces.push((start..range.end, start));
+        }
+        pieces
+    }
+
+    fn normalized(mut mappings: Vec<(Range<u64>, u64)>) -> SeedMap {
+        mappings.sort_by_key(|(source, _)| source.start);
+        let mut disjoint: Vec<(Range<u64>, u64)> = Vec::with_capacity(mappings.len());
+        for (mut source, mut destination) in mappings {
+            if let Some((last, _)) = disjoint.last() {
+                if source.start < last.end {
+                    destination += last.end.min(source.end) - source.start;
+                    source.start = last.end.min(source.end);
+                }
+            }
+            if !source.is_empty() {
+                disjoint.push((source, destination));
+            }
+        }
+        let mut canonical: Vec<(Range<u64>, u64)> = Vec::with_capacity(disjoint.len());
+        for (source, destination) in disjoint {
+            if source.start == destination {
+                continue;
+            }
+            match canonical.last_mut() {
+                Some((last, last_destination))
+                    if last.end == source.start
+                        && *last_destination + (last.end - last.start) == destination =>
+                {
+                    last.end = source.end;
+                }
+                _ => canonical.push((source, destination)),
+            }
+        }
+        SeedMap {
+            mappings: canonical,
+        }
+    }
+}
+
+/// Composes the maps in order, e.g. the seven maps of an almanac into the one
+/// from seeds to locations.
+impl<'a> FromIterator<&'a SeedMap> for SeedMap {
+    fn from_iter<I: IntoIterator<Item = &'a SeedMap>>(maps: I) -> Self {
+        maps.into_iter()
+            .fold(SeedMap::default(), |composed, map| composed.compose(map))
+    }
+}
+
+/// A set of numbers stored as sorted, disjoint and non-empty intervals, so a
+/// whole seed range goes through a [`SeedMap`] in one step per mapping it touches.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RangeMap {
+    ranges: Vec<Range<u64>>,
+}
+
+impl RangeMap {
+    pub fn new(ranges: impl IntoIterator<Item = Range<u64>>) -> Self {
+        let mut ranges = ranges
+            .into_iter()
+            .filter(|range| !range.is_empty())
+            .collect::<Vec<Range<u64>>>();
+        ranges.sort_unstable_by_key(|range| range.start);
+        let mut merged: Vec<Range<u64>> = Vec::with_capacity(ranges.len());
+        for range in ranges {
+            match merged.last_mut() {
+                Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+                _ => merged.push(range),
+            }
+        }
+        RangeMap { ranges: merged }
+    }
+
+    /// The image of the set under `seed_map`.
+    pub fn apply(&self, seed_map: &SeedMap) -> Self {
+        RangeMap::new(self.ranges.iter().flat_map(|range| {
+            seed_map
+                .split(range.clone())
+                .into_iter()
+                .map(|(source, destination)| destination..destination + (source.end - source.start))
+        }))
+    }
+
+    pub fn ranges(&self) -> &[Range<u64>] {
+        &self.ranges
+    }
+
+    pub fn min(&self) -> Option<u64> {
+        self.ranges.first().map(|range| range.start)
+    }
+}
+
+/// The end of the destination range of `source`, `None` when it does not fit
+/// in a `u64`.
+fn destination_end(source: &Range<u64>, destination: u64) -> Option<u64> {
+    destination.checked_add(source.end.saturating_sub(source.start))
+}
+
+/// One `destination source length` line, failing on the line when either
+/// range does not fit in a `u64`.
+fn create_map(input: &str) -> IResult<&str, (Range<u64>, u64)> {
+    let (rest, (destination, source, range)) = tuple((
+        complete::u64,
+        complete::u64.preceded_by(tag(" ")),
+        complete::u64.preceded_by(tag(" ")),
+    ))(input)?;
+
+    match source.checked_add(range).map(|end| source..end) {
+        Some(source) if destination_end(&source, destination).is_some() => {
+            Ok((rest, (source, destination)))
+        }
+        _ => Err(nom::Err::Failure(Error::new(input, ErrorKind::TooLarge))),
+    }
+}
+
+#[tracing::instrument]
+pub(crate) fn seed_map(input: &str) -> IResult<&str, SeedMap> {
+    take_until("map:")
+        .precedes(tag("map:"))
+        .precedes(many1(line_ending.precedes(create_map)).map(SeedMap::normalized))
+        .parse(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    fn example() -> Vec<SeedMap> {
+        [
+            vec![(98..100, 50), (50..98, 52)],
+            vec![(15..52, 0), (52..54, 37), (0..15, 39)],
+            vec![(53..61, 49), (11..53, 0), (0..7, 42), (7..11, 57)],
+            vec![(18..25, 88), (25..95, 18)],
+            vec![(77..100, 45), (45..64, 81), (64..77, 68)],
+            vec![(69..70, 0), (0..69, 1)],
+            vec![(56..93, 60), (93..97, 56)],
+        ]
+        .into_iter()
+        .map(|mappings| SeedMap::new(mappings).unwrap())
+        .collect()
+    }
+
+    #[test]
+    fn test_compose_almanac() {
+        let maps = example();
+        let seed_to_location = maps.iter().collect::<SeedMap>();
+        for (seed, location) in [(79, 82), (14, 43), (55, 86), (13, 35)] {
+            assert_eq!(location, seed_to_location.get(seed));
+        }
+        let location_to_seed = seed_to_location.invert().unwrap();
+        assert_eq!(79, location_to_seed.get(82));
+        assert_eq!(seed_to_location, location_to_seed.invert().unwrap());
+    }
+
+    #[test]
+    fn test_segments() {
+        let seed_map = SeedMap::new([(98..100, 50), (50..98, 52)]).unwrap();
+        assert_eq!(
+            vec![
+                (0..50, 0..50),
+                (50..98, 52..100),
+                (98..100, 50..52),
+                (100..u64::MAX, 100..u64::MAX)
+            ],
+            seed_map.segments()
+        );
+        assert_eq!(
+            vec![(0..u64::MAX, 0..u64::MAX)],
+            SeedMap::default().segments()
+        );
+    }
+
+    #[test]
+    fn test_normalized() {
+        let seed_map =
+            SeedMap::new([(10..20, 110), (0..5, 0), (15..30, 115), (20..25, 0)]).unwrap();
+        assert_eq!(vec![(10..30, 110)], seed_map.mappings);
+        assert_eq!(None, SeedMap::new([(0..10, 20)]).unwrap().invert());
+        assert_eq!(
+            None,
+            SeedMap::new([(0..10, 10), (10..20, 5)]).unwrap().invert()
+        );
+    }
+
+    #[test]
+    fn test_destination_overflow() {
+        assert!(matches!(
+            SeedMap::new([(0..10, u64::MAX - 5)]),
+            Err(AocError::InvalidInput(_))
+        ));
+        assert!(SeedMap::new([(0..10, u64::MAX - 10)]).is_ok());
+        let input = "seed-to-soil map:\n50 98 2\n18446744073709551615 0 2";
+        match aoc_core::parse::finish(input, seed_map).unwrap_err() {
+            AocError::ParseError { line, column, .. } => assert_eq!((3, 1), (line, column)),
+            error => panic!("expected a parse error, got {error:?}"),
+        }
+    }
+
+    #[test]
+    fn test_range_map_splits_at_mapping_boundaries() {
+        let seed_map = SeedMap::new([(98..100, 50), (50..98, 52)]).unwrap();
+        let ranges = RangeMap::new([79..93, 40..60, 95..110]).apply(&seed_map);
+        assert_eq!(&[40..62, 81..95, 97..110][..], ranges.ranges());
+        assert_eq!(Some(40), ranges.min());
+    }
+
+    #[test]
+    fn test_range_map_merges_overlapping_ranges() {
+        let ranges = RangeMap::new([10..20, 5..5, 15..30, 30..31, 40..41]);
+        assert_eq!(&[10..31, 40..41][..], ranges.ranges());
+    }
+
+    fn seed_maps() -> impl Strategy<Value = SeedMap> {
+        prop::collection::vec((0..200_u64, 0..50_u64, 0..200_u64), 0..8).prop_map(|mappings| {
+            SeedMap::new(
+                mappings
+                    .into_iter()
+                    .map(|(source, len, destination)| (source..source + len, destination)),
+            )
+            .unwrap()
+        })
+    }
+
+    /// Maps that move whole blocks of `0..n` around, which can always be inverted
+    fn permutations() -> impl Strategy<Value = SeedMap> {
+        prop::collection::vec(1..30_u64, 1..8)
+            .prop_flat_map(|lens| {
+                let blocks = lens.len();
+                (
+                    Just(lens),
+                    Just((0..blocks).collect::<Vec<usize>>()).prop_shuffle(),
+                )
+            })
+            .prop_map(|(lens, order)| {
+                let starts = lens
+                    .iter()
+                    .scan(0, |start, len| {
+                        *start += len;
+                        Some(*start - len)
+                    })
+                    .collect::<Vec<u64>>();
+                let mut destination = 0;
+                let mut mappings = Vec::new();
+                for block in order {
+                    mappings.push((starts[block]..starts[block] + lens[block], destination));
+                    destination += lens[block];
+                }
+                SeedMap::new(mappings).unwrap()
+            })
+    }
+
+    proptest! {
+        #[test]
+        fn test_compose_applies_both_maps(a in seed_maps(), b in seed_maps(), x in 0..500_u64) {
+            prop_assert_eq!(b.get(a.get(x)), a.compose(&b).get(x));
+        }
+
+        #[test]
+        fn test_segments_agree_with_get(a in seed_maps(), x in 0..500_u64) {
+            let segments = a.segments();
+            let (source, destination) = segments
+                .iter()
+                .find(|(source, _)| source.contains(&x))
+                .unwrap();
+            prop_assert_eq!(destination.start + (x - source.start), a.get(x));
+        }
+
+        #[test]
+        fn test_invert_undoes_the_map(a in permutations(), x in 0..500_u64) {
+            let inverse = a.invert().unwrap();
+            prop_assert_eq!(x, inverse.get(a.get(x)));
+            prop_assert_eq!(x, a.get(inverse.get(x)));
+        }
+    }
+}
@@ -2,3 +2,7 @@ pub mod custom_error;
 
 pub mod part1;
 pub mod part2;
+pub mod race;
+
+pub const DAY: u8 = 6;
+pub const TITLE: &str = "Wait For It";
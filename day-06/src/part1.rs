@@ -1,4 +1,7 @@
+use aoc_core::{parse::finish, Solution};
+
 use crate::custom_error::AocError;
+use crate::race::ways_to_win;
 
 use nom::{
     bytes::complete::is_not,
@@ -15,22 +18,68 @@ fn nums(input: &str) -> IResult<&str, Vec<u32>> {
         .parse(input)
 }
 
-fn parse(input: &str) -> IResult<&str, (Vec<u32>, Vec<u32>)> {
+fn parse_races(input: &str) -> IResult<&str, (Vec<u32>, Vec<u32>)> {
     separated_pair(nums, line_ending, nums).parse(input)
 }
 
+#[derive(Debug)]
+pub struct Race {
+    pub time: u32,
+    pub distance: u32,
+}
+
 #[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let (_, (time, distance)) = parse(input).expect("Should be valid");
-    let res = time.into_iter().zip(distance).fold(1, |acc, (x, y)| {
-        acc * (1..x).fold(0, |poss, k| match ((x - k) * k).cmp(&y) {
-            std::cmp::Ordering::Greater => poss + 1,
-            _ => poss,
+pub fn parse(input: &str) -> miette::Result<Vec<Race>, AocError> {
+    let (time, distance) = finish(input, parse_races)?;
+    if time.len() != distance.len() {
+        return Err(AocError::invalid_input(format!(
+            "{} race times but {} distances",
+            time.len(),
+            distance.len()
+        )));
+    }
+    Ok(time
+        .into_iter()
+        .zip(distance)
+        .map(|(time, distance)| Race { time, distance })
+        .collect())
+}
+
+#[tracing::instrument(skip(races))]
+pub fn solve(races: &[Race]) -> miette::Result<String, AocError> {
+    let res = races
+        .iter()
+        .try_fold(1_u64, |acc, race| {
+            acc.checked_mul(ways_to_win(u64::from(race.time), u64::from(race.distance))?)
         })
-    });
+        .ok_or_else(|| AocError::invalid_input("the product of the ways to win overflows"))?;
     Ok(res.to_string())
 }
 
+#[tracing::instrument]
+pub fn process(input: &str) -> miette::Result<String, AocError> {
+    solve(&parse(input)?)
+}
+
+pub struct Part1;
+
+impl Solution for Part1 {
+    const DAY: u8 = crate::DAY;
+    const PART: u8 = 1;
+    const TITLE: &'static str = crate::TITLE;
+
+    type Model<'a> = Vec<Race>;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Self::Model<'_>, AocError> {
+        parse(input)
+    }
+
+    fn solve(races: &Self::Model<'_>, _params: &Self::Params) -> Result<String, AocError> {
+        solve(races)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,8 +1,12 @@
+use aoc_core::{parse::finish, Solution};
+
 use crate::custom_error::AocError;
+use crate::race::ways_to_win;
 
 use nom::{
     bytes::complete::is_not,
     character::complete::{digit1, line_ending, space1},
+    combinator::map_res,
     multi::separated_list1,
     sequence::separated_pair,
     IResult, Parser,
@@ -10,28 +14,58 @@ use nom::{
 use nom_supreme::ParserExt;
 
 fn nums(input: &str) -> IResult<&str, u64> {
-    is_not("0123456789")
-        .precedes(separated_list1(space1, digit1))
-        .map(|list| list.join("").parse::<u64>().expect("Should be valid"))
-        .parse(input)
+    map_res(
+        is_not("0123456789").precedes(separated_list1(space1, digit1)),
+        |list| list.join("").parse::<u64>(),
+    )(input)
 }
 
-fn parse(input: &str) -> IResult<&str, (u64, u64)> {
+fn parse_race(input: &str) -> IResult<&str, (u64, u64)> {
     separated_pair(nums, line_ending, nums).parse(input)
 }
 
+#[derive(Debug)]
+pub struct Race {
+    pub time: u64,
+    pub distance: u64,
+}
+
+#[tracing::instrument]
+pub fn parse(input: &str) -> miette::Result<Race, AocError> {
+    let (time, distance) = finish(input, parse_race)?;
+    Ok(Race { time, distance })
+}
+
+#[tracing::instrument(skip(race))]
+pub fn solve(race: &Race) -> miette::Result<String, AocError> {
+    let &Race { time, distance } = race;
+    let res = ways_to_win(u128::from(time), u128::from(distance))
+        .ok_or_else(|| AocError::invalid_input("the race is too long"))?;
+    Ok(res.to_string())
+}
+
 #[tracing::instrument]
 pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let (_, (test, distance)) = parse(input).expect("Should be valid");
-    let mut k = 1_u64;
-    let mut res = 0_u64;
-    while k < test {
-        if ((test - k) * k) > distance {
-            res += 1;
-        }
-        k += 1;
+    solve(&parse(input)?)
+}
+
+pub struct Part2;
+
+impl Solution for Part2 {
+    const DAY: u8 = crate::DAY;
+    const PART: u8 = 2;
+    const TITLE: &'static str = crate::TITLE;
+
+    type Model<'a> = Race;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Self::Model<'_>, AocError> {
+        parse(input)
+    }
+
+    fn solve(race: &Self::Model<'_>, _params: &Self::Params) -> Result<String, AocError> {
+        solve(race)
     }
-    Ok(res.to_string())
 }
 
 #[cfg(test)]
@@ -0,0 +1,109 @@
+use std::ops::{Add, Div, Mul, Rem, Sub};
+
+/// The unsigned integers a race can be solved in.
+pub trait RaceTime:
+    Copy
+    + Ord
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+    const TWO: Self;
+
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+
+    /// The largest integer whose square is at most `self`
+    fn isqrt(self) -> Self;
+}
+
+macro_rules! race_time {
+    ($($int:ty),*) => {
+        $(
+            impl RaceTime for $int {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+                const TWO: Self = 2;
+
+                fn checked_mul(self, rhs: Self) -> Option<Self> {
+                    <$int>::checked_mul(self, rhs)
+                }
+
+                fn isqrt(self) -> Self {
+                    <$int>::isqrt(self)
+                }
+            }
+        )*
+    };
+}
+
+race_time!(u64, u128);
+
+/// Counts the hold times `k` in `0..=time` that beat the record, i.e. the
+/// integers strictly between the roots of `k * (time - k) = distance`.
+///
+/// With `time = 2 * half + odd`, holding for `half - j` or `half + odd + j`
+/// travels `half * (half + odd) - j * (j + odd)`, so the record falls for every
+/// `j` with `j * (j + odd) < slack` where `slack` is how far the longest hold
+/// beats the record. `None` when that longest distance does not fit in `T`.
+pub fn ways_to_win<T: RaceTime>(time: T, distance: T) -> Option<T> {
+    let half = time / T::TWO;
+    let odd = time % T::TWO;
+    let farthest = half.checked_mul(half + odd)?;
+    if farthest <= distance {
+        return Some(T::ZERO);
+    }
+    let slack = farthest - distance;
+    let mut j = slack.isqrt();
+    // `j * j <= slack`, one step back when the root is exact or `odd` pushes it over
+    if j.checked_mul(j + odd)
+        .is_none_or(|travelled| travelled >= slack)
+    {
+        j = j - T::ONE;
+    }
+    Some(T::TWO * j + T::ONE + odd)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn brute_force(time: u64, distance: u64) -> u64 {
+        (0..=time).filter(|k| k * (time - k) > distance).count() as u64
+    }
+
+    #[test]
+    fn test_examples() {
+        assert_eq!(Some(4), ways_to_win(7_u64, 9));
+        assert_eq!(Some(8), ways_to_win(15_u64, 40));
+        // 10 * 20 == 200, the roots are integers and do not count
+        assert_eq!(Some(9), ways_to_win(30_u64, 200));
+        assert_eq!(Some(71503), ways_to_win(71530_u128, 940200));
+    }
+
+    #[test]
+    fn test_against_brute_force() {
+        for time in 0..60 {
+            for distance in 0..time * time / 4 + 2 {
+                assert_eq!(
+                    Some(brute_force(time, distance)),
+                    ways_to_win(time, distance),
+                    "time {time}, distance {distance}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_large_races() {
+        let time = u64::MAX as u128;
+        assert_eq!(Some(time - 1), ways_to_win(time, 0));
+        assert_eq!(Some(1), ways_to_win(1_u64 << 32, (1 << 62) - 1));
+        assert_eq!(Some(0), ways_to_win(1_u64 << 32, 1 << 62));
+        assert_eq!(None, ways_to_win(u64::MAX, 0));
+        assert_eq!(None, ways_to_win(u128::MAX, u128::MAX));
+    }
+}
@@ -2,3 +2,7 @@ pub mod custom_error;
 
 pub mod part1;
 pub mod part2;
+pub mod ranking;
+
+pub const DAY: u8 = 7;
+pub const TITLE: &str = "Camel Cards";
@@ -1,90 +1,45 @@
-use std::collections::{BTreeMap, HashMap};
-
-use itertools::Itertools;
+use aoc_core::Solution;
 
 use crate::custom_error::AocError;
-const HAND_TYPE: [&str; 7] = ["11111", "1112", "122", "113", "23", "14", "5"];
+use crate::ranking::{split_hand, total_winnings, Hand, Ranking};
 
-#[derive(Debug)]
-struct CamelCard {
-    card_type: u8,
-    card: (u8, u8, u8, u8, u8),
-}
+const RANKING: Ranking = Ranking::new("AKQJT98765432", "", 5);
 
-impl Ord for CamelCard {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        match self.card_type.cmp(&other.card_type) {
-            std::cmp::Ordering::Less => std::cmp::Ordering::Less,
-            std::cmp::Ordering::Equal => self.card.cmp(&other.card),
-            std::cmp::Ordering::Greater => std::cmp::Ordering::Greater,
-        }
-    }
+#[tracing::instrument]
+pub fn parse(input: &str) -> miette::Result<Vec<(Hand, u32)>, AocError> {
+    input
+        .lines()
+        .map(|line| split_hand(&RANKING, input, line))
+        .collect()
 }
 
-impl PartialOrd for CamelCard {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.cmp(other))
-    }
+#[tracing::instrument(skip(hands))]
+pub fn solve(hands: &[(Hand, u32)]) -> miette::Result<String, AocError> {
+    Ok(total_winnings(hands)?.to_string())
 }
 
-impl PartialEq for CamelCard {
-    fn eq(&self, other: &Self) -> bool {
-        self.card_type == other.card_type && self.card == other.card
-    }
+#[tracing::instrument]
+pub fn process(input: &str) -> miette::Result<String, AocError> {
+    solve(&parse(input)?)
 }
 
-impl Eq for CamelCard {}
+pub struct Part1;
 
-fn calculate_card_type(input: &str) -> u8 {
-    let mut my_hash: HashMap<u8, i32> = HashMap::new();
-    input.as_bytes().iter().for_each(|v| {
-        my_hash.entry(*v).and_modify(|k| *k += 1).or_insert(1);
-    });
-    let hand = my_hash.values().sorted_unstable().join("");
-    for (idx, k) in HAND_TYPE.iter().enumerate() {
-        if k == &hand.as_str() {
-            return idx as u8;
-        }
-    }
-    unreachable!()
-}
+impl Solution for Part1 {
+    const DAY: u8 = crate::DAY;
+    const PART: u8 = 1;
+    const TITLE: &'static str = crate::TITLE;
 
-fn generate_tuple(input: &str) -> (u8, u8, u8, u8, u8) {
-    input
-        .chars()
-        .map(|card| match card {
-            'A' => 14,
-            'K' => 13,
-            'Q' => 12,
-            'J' => 11,
-            'T' => 10,
-            value => value.to_digit(10).unwrap() as u8,
-        })
-        .collect_tuple::<(u8, u8, u8, u8, u8)>()
-        .unwrap()
-}
+    type Model<'a> = Vec<(Hand, u32)>;
+    type Params = ();
 
-#[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let mut map = input.lines().fold(
-        BTreeMap::new(),
-        |mut acc: BTreeMap<CamelCard, u32>, line| {
-            let (card, bid) = line.split_once(' ').unwrap();
-            let camel_card = CamelCard {
-                card_type: calculate_card_type(card),
-                card: generate_tuple(card),
-            };
-            acc.insert(camel_card, bid.parse::<u32>().unwrap());
-            acc
-        },
-    );
-    let mut count = 1;
-    let mut res = 0;
-    while let Some((_, bid)) = map.pop_first() {
-        res += bid * count;
-        count += 1;
+    fn parse(input: &str) -> Result<Self::Model<'_>, AocError> {
+        parse(input)
+    }
+
+    fn solve(hands: &Self::Model<'_>, _params: &Self::Params) -> Result<String, AocError> {
+        solve(hands)
     }
-    Ok(res.to_string())
 }
 
 #[cfg(test)]
@@ -101,4 +56,25 @@ QQQJA 483";
         assert_eq!("6440", process(input)?);
         Ok(())
     }
+
+    #[test]
+    fn test_parse_error() {
+        let error = parse("32T3K 765\nT55X5 684").unwrap_err();
+        assert!(matches!(
+            error,
+            AocError::ParseError {
+                line: 2,
+                column: 4,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_non_ascii_hand() {
+        assert!(matches!(
+            parse("32T3\u{e9} 765"),
+            Err(AocError::ParseError { .. })
+        ));
+    }
 }
@@ -0,0 +1,265 @@
+use std::cmp::Ordering;
+
+use crate::custom_error::AocError;
+
+/// The kind of a hand, weakest first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Category {
+    HighCard,
+    OnePair,
+    TwoPair,
+    ThreeOfAKind,
+    FullHouse,
+    FourOfAKind,
+    FiveOfAKind,
+    /// Six or more of the same card, only possible in hands larger than five
+    ManyOfAKind(usize),
+}
+
+impl Category {
+    /// Classifies a hand by the sizes of its groups of equal cards, largest first.
+    fn from_groups(groups: &[usize]) -> Self {
+        match (groups[0], groups.get(1).copied().unwrap_or(0)) {
+            (1, _) => Category::HighCard,
+            (2, 2..) => Category::TwoPair,
+            (2, _) => Category::OnePair,
+            (3, 2..) => Category::FullHouse,
+            (3, _) => Category::ThreeOfAKind,
+            (4, _) => Category::FourOfAKind,
+            (5, _) => Category::FiveOfAKind,
+            (size, _) => Category::ManyOfAKind(size),
+        }
+    }
+}
+
+/// What hands are compared by: the group sizes after the wildcards joined the
+/// best group, then the strength of every card in the order they were dealt.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct HandKey {
+    groups: Vec<usize>,
+    strengths: Vec<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hand {
+    pub cards: String,
+    pub category: Category,
+    /// The cards with every wildcard replaced by the card that reaches the best category
+    pub best: String,
+    key: HandKey,
+}
+
+impl Hand {
+    pub fn key(&self) -> &HandKey {
+        &self.key
+    }
+}
+
+impl Ord for Hand {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key.cmp(&other.key)
+    }
+}
+
+impl PartialOrd for Hand {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// The rules of a game of Camel Cards.
+#[derive(Debug, Clone, Copy)]
+pub struct Ranking<'a> {
+    order: &'a str,
+    wildcards: &'a str,
+    hand_size: usize,
+}
+
+impl<'a> Ranking<'a> {
+    /// `order` lists every card from the strongest to the weakest, `wildcards`
+    /// are the cards that act like whichever card makes the hand strongest.
+    pub const fn new(order: &'a str, wildcards: &'a str, hand_size: usize) -> Self {
+        assert!(hand_size > 0, "a hand needs at least one card");
+        Ranking {
+            order,
+            wildcards,
+            hand_size,
+        }
+    }
+
+    pub fn order(&self) -> &'a str {
+        self.order
+    }
+
+    pub fn hand_size(&self) -> usize {
+        self.hand_size
+    }
+
+    /// Higher is stronger, `None` for a card that is not in the game
+    pub fn strength(&self, card: char) -> Option<usize> {
+        self.order
+            .chars()
+            .position(|known| known == card)
+            .map(|position| self.order.chars().count() - position)
+    }
+
+    /// Ranks `cards`, `None` when one of them is unknown or there are not
+    /// [`hand_size`](Self::hand_size) of them.
+    pub fn rank(&self, cards: &str) -> Option<Hand> {
+        let strengths = cards
+            .chars()
+            .map(|card| self.strength(card))
+            .collect::<Option<Vec<usize>>>()?;
+        if strengths.len() != self.hand_size {
+            return None;
+        }
+        let is_wild = |card: char| self.wildcards.contains(card);
+        let mut counts = vec![0; self.order.chars().count() + 1];
+        for (card, strength) in cards.chars().zip(&strengths) {
+            if !is_wild(card) {
+                counts[*strength] += 1;
+            }
+        }
+        let wild = cards.chars().filter(|card| is_wild(*card)).count();
+        // The wildcards join the largest group, the strongest card when there is none
+        let target = self
+            .order
+            .chars()
+            .filter(|card| !is_wild(*card))
+            .max_by_key(|card| {
+                let strength = self.strength(*card).unwrap_or_default();
+                (counts[strength], strength)
+            });
+        let best = match target {
+            Some(target) => {
+                counts[self.strength(target).unwrap_or_default()] += wild;
+                cards
+                    .chars()
+                    .map(|card| if is_wild(card) { target } else { card })
+                    .collect()
+            }
+            None => {
+                counts[0] += wild;
+                cards.to_string()
+            }
+        };
+        let mut groups = counts
+            .into_iter()
+            .filter(|count| *count > 0)
+            .collect::<Vec<usize>>();
+        groups.sort_unstable_by(|a, b| b.cmp(a));
+        Some(Hand {
+            cards: cards.to_string(),
+            category: Category::from_groups(&groups),
+            best,
+            key: HandKey { groups, strengths },
+        })
+    }
+}
+
+/// Splits a line of `input` into its hand under `ranking` and the bid, checking
+/// every card is a known one
+pub fn split_hand(ranking: &Ranking, input: &str, line: &str) -> Result<(Hand, u32), AocError> {
+    let (cards, bid) = line
+        .split_once(' ')
+        .ok_or_else(|| AocError::parse_error(input, line, "expected a hand and a bid"))?;
+    if let Some((position, ch)) = cards
+        .char_indices()
+        .find(|(_, ch)| ranking.strength(*ch).is_none())
+    {
+        return Err(AocError::parse_error(
+            input,
+            &cards[position..position + ch.len_utf8()],
+            format!("expected one of `{}`", ranking.order()),
+        ));
+    }
+    let hand = ranking.rank(cards).ok_or_else(|| {
+        AocError::parse_error(
+            input,
+            cards,
+            format!("expected {} cards", ranking.hand_size()),
+        )
+    })?;
+    let bid = bid
+        .parse::<u32>()
+        .map_err(|_| AocError::parse_error(input, bid, "expected a bid"))?;
+    Ok((hand, bid))
+}
+
+/// The sum of every bid times the rank of its hand, the weakest hand ranked 1.
+pub fn total_winnings(hands: &[(Hand, u32)]) -> Result<u64, AocError> {
+    let mut hands = hands.iter().collect::<Vec<_>>();
+    hands.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+    let mut res = 0_u64;
+    for (count, (_, bid)) in (1_u64..).zip(hands) {
+        res = res
+            .checked_add(u64::from(*bid) * count)
+            .ok_or_else(|| AocError::invalid_input("total winnings overflow"))?;
+    }
+    Ok(res)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STANDARD: Ranking = Ranking::new("AKQJT98765432", "", 5);
+    const JOKERS: Ranking = Ranking::new("AKQT98765432J", "J", 5);
+
+    #[test]
+    fn test_categories() {
+        let categories = [
+            "32T3K", "T55J5", "KK677", "KTJJT", "QQQJA", "AAAAA", "23332", "23456",
+        ]
+        .map(|cards| STANDARD.rank(cards).unwrap().category);
+        assert_eq!(
+            [
+                Category::OnePair,
+                Category::ThreeOfAKind,
+                Category::TwoPair,
+                Category::TwoPair,
+                Category::ThreeOfAKind,
+                Category::FiveOfAKind,
+                Category::FullHouse,
+                Category::HighCard,
+            ],
+            categories
+        );
+    }
+
+    #[test]
+    fn test_jokers() {
+        let hand = JOKERS.rank("KTJJT").unwrap();
+        assert_eq!(
+            (Category::FourOfAKind, "KTTTT"),
+            (hand.category, &*hand.best)
+        );
+        let hand = JOKERS.rank("JJJJJ").unwrap();
+        assert_eq!(
+            (Category::FiveOfAKind, "AAAAA"),
+            (hand.category, &*hand.best)
+        );
+        assert_eq!(Category::FullHouse, JOKERS.rank("2J233").unwrap().category);
+        // A joker is the weakest card when breaking ties
+        assert!(JOKERS.rank("JKKK2").unwrap() < JOKERS.rank("QQQQ2").unwrap());
+        assert!(STANDARD.rank("JKKK2").unwrap() < STANDARD.rank("QQQQ2").unwrap());
+    }
+
+    #[test]
+    fn test_custom_rules() {
+        let wild = Ranking::new("abcd", "cd", 3);
+        let hand = wild.rank("dbc").unwrap();
+        assert_eq!(
+            (Category::ThreeOfAKind, "bbb"),
+            (hand.category, &*hand.best)
+        );
+        assert!(wild.rank("abcd").is_none());
+        assert!(wild.rank("abe").is_none());
+
+        let large = Ranking::new("AKQJT98765432", "J", 7);
+        let hand = large.rank("2J2J2J2").unwrap();
+        assert_eq!(Category::ManyOfAKind(7), hand.category);
+        assert!(large.rank("AAAAAAK").unwrap() < hand);
+        assert!(large.rank("AAAAKKK").unwrap() > large.rank("AAAAKQT").unwrap());
+    }
+}
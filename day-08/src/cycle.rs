@@ -0,0 +1,215 @@
+use std::{collections::HashMap, hash::Hash};
+
+/// The walk of one ghost. Its state is the node it stands on together with the
+/// index of the next instruction, so after `offset` steps it repeats every `length`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cycle {
+    /// Steps taken before the first state that repeats
+    pub offset: u64,
+    pub length: u64,
+    /// Steps before `offset` that end on a goal, these never happen again
+    pub lead_in: Vec<u64>,
+    /// Steps in `offset..offset + length` that end on a goal, each one comes back
+    /// every `length` steps
+    pub hits: Vec<u64>,
+}
+
+impl Cycle {
+    /// Walks from `start` until a state repeats. `step` gets the current node and
+    /// the index of the instruction to follow, out of `period` instructions.
+    pub fn find<S: Copy + Eq + Hash>(
+        start: S,
+        period: usize,
+        mut step: impl FnMut(S, usize) -> S,
+        is_goal: impl Fn(S) -> bool,
+    ) -> Self {
+        let mut seen = HashMap::new();
+        let mut goals = Vec::new();
+        let mut node = start;
+        for steps in 0_u64.. {
+            let instruction = (steps % period as u64) as usize;
+            if let Some(&offset) = seen.get(&(node, instruction)) {
+                let split = goals.partition_point(|goal| *goal < offset);
+                let hits = goals.split_off(split);
+                return Cycle {
+                    offset,
+                    length: steps - offset,
+                    lead_in: goals,
+                    hits,
+                };
+            }
+            seen.insert((node, instruction), steps);
+            if is_goal(node) {
+                goals.push(steps);
+            }
+            node = step(node, instruction);
+        }
+        unreachable!("a walk on finitely many states has to repeat")
+    }
+
+    /// Whether the walk stands on a goal after `steps` steps.
+    pub fn hits_at(&self, steps: u64) -> bool {
+        if steps < self.offset {
+            return self.lead_in.binary_search(&steps).is_ok();
+        }
+        let in_cycle = self.offset + (steps - self.offset) % self.length;
+        self.hits.binary_search(&in_cycle).is_ok()
+    }
+
+    pub fn first_hit(&self) -> Option<u64> {
+        self.lead_in.first().or(self.hits.first()).copied()
+    }
+}
+
+/// The first number of steps after which every walk stands on a goal, `None`
+/// when that never happens.
+pub fn first_common_hit(cycles: &[Cycle]) -> Option<u128> {
+    let (first, rest) = cycles.split_first()?;
+    // Before every walk is in its cycle only the goals of the first walk have
+    // to be checked, there are finitely many of them
+    let settled = cycles.iter().map(|cycle| cycle.offset).max()?;
+    let mut early = first.lead_in.clone();
+    for hit in &first.hits {
+        early.extend((*hit..settled).step_by(first.length as usize));
+    }
+    early.sort_unstable();
+    if let Some(steps) = early
+        .into_iter()
+        .find(|steps| rest.iter().all(|cycle| cycle.hits_at(*steps)))
+    {
+        return Some(u128::from(steps));
+    }
+    // From then on a walk is on a goal exactly when the steps are congruent to
+    // one of its hits, so every combination of hits is a system of congruences
+    let mut congruences = vec![(0_u128, 1_u128)];
+    for cycle in cycles {
+        congruences = congruences
+            .iter()
+            .flat_map(|&congruence| {
+                cycle.hits.iter().filter_map(move |hit| {
+                    let length = u128::from(cycle.length);
+                    crt(congruence, (u128::from(*hit) % length, length))
+                })
+            })
+            .collect();
+        congruences.sort_unstable();
+        congruences.dedup();
+    }
+    let settled = u128::from(settled);
+    congruences
+        .into_iter()
+        .map(|(residue, modulus)| match residue < settled {
+            true => residue + (settled - residue).div_ceil(modulus) * modulus,
+            false => residue,
+        })
+        .min()
+}
+
+/// Generalised Chinese remainder theorem: the `x mod lcm(m1, m2)` with
+/// `x ≡ r1 (mod m1)` and `x ≡ r2 (mod m2)`, `None` when there is no such `x`.
+/// The moduli do not have to be coprime, `r1 < m1` and `r2 < m2`.
+pub fn crt((r1, m1): (u128, u128), (r2, m2): (u128, u128)) -> Option<(u128, u128)> {
+    let g = gcd(m1, m2);
+    let diff = (r2 + (m2 - r1 % m2)) % m2;
+    if diff % g != 0 {
+        return None;
+    }
+    let m2g = m2 / g;
+    let k = mul_mod(diff / g, inverse((m1 / g) % m2g, m2g)?, m2g);
+    let lcm = m1.checked_mul(m2g)?;
+    Some((r1 + m1 * k, lcm))
+}
+
+fn gcd(a: u128, b: u128) -> u128 {
+    if b == 0 {
+        return a;
+    }
+    gcd(b, a % b)
+}
+
+/// The inverse of `a` modulo `m`, for coprime `a` and `m`
+fn inverse(a: u128, m: u128) -> Option<u128> {
+    if m == 1 {
+        return Some(0);
+    }
+    let (mut old_r, mut r) = (i128::try_from(a).ok()?, i128::try_from(m).ok()?);
+    let (mut old_s, mut s) = (1_i128, 0_i128);
+    while r != 0 {
+        let quotient = old_r / r;
+        (old_r, r) = (r, old_r - quotient * r);
+        (old_s, s) = (s, old_s - quotient * s);
+    }
+    (old_r == 1).then(|| old_s.rem_euclid(m as i128) as u128)
+}
+
+/// `a * b % m` without overflowing
+fn mul_mod(a: u128, mut b: u128, m: u128) -> u128 {
+    let add_mod = |a: u128, b: u128| if a >= m - b { a - (m - b) } else { a + b };
+    let (mut a, mut product) = (a % m, 0);
+    while b > 0 {
+        if b & 1 == 1 {
+            product = add_mod(product, a);
+        }
+        a = add_mod(a, a);
+        b >>= 1;
+    }
+    product
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A walk over `0..nodes` that moves one node on every step and wraps to `back`
+    fn ring(nodes: u64, back: u64, goals: &[u64]) -> Cycle {
+        Cycle::find(
+            0_u64,
+            1,
+            |node, _| if node + 1 == nodes { back } else { node + 1 },
+            |node| goals.contains(&node),
+        )
+    }
+
+    #[test]
+    fn test_find() {
+        let cycle = ring(7, 3, &[1, 4, 6]);
+        assert_eq!(
+            Cycle {
+                offset: 3,
+                length: 4,
+                lead_in: vec![1],
+                hits: vec![4, 6],
+            },
+            cycle
+        );
+        assert!(cycle.hits_at(1) && cycle.hits_at(8) && cycle.hits_at(10));
+        assert!(!cycle.hits_at(5) && !cycle.hits_at(9));
+    }
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(Some((23, 105)), crt(crt((2, 3), (3, 5)).unwrap(), (2, 7)));
+        assert_eq!(Some((10, 12)), crt((4, 6), (2, 4)));
+        assert_eq!(None, crt((1, 4), (0, 6)));
+        assert_eq!(Some((5, 7)), crt((0, 1), (5, 7)));
+    }
+
+    #[test]
+    fn test_first_common_hit() {
+        // Offsets and two goals per cycle, the first walk is on a goal after 1,
+        // 4, 6, 8, 10, ... steps and the second after 2, 4, 7, 9, 12, ...
+        let first = ring(7, 3, &[1, 4, 6]);
+        let second = ring(5, 0, &[2, 4]);
+        assert_eq!(Some(4), first_common_hit(&[first.clone(), second.clone()]));
+        let third = ring(3, 0, &[0]);
+        assert_eq!(Some(12), first_common_hit(&[first, second, third]));
+    }
+
+    #[test]
+    fn test_never_in_sync() {
+        let even = ring(2, 0, &[0]);
+        let odd = ring(2, 0, &[1]);
+        assert_eq!(None, first_common_hit(&[even, odd]));
+        assert_eq!(None, first_common_hit(&[ring(3, 0, &[1]), ring(2, 0, &[])]));
+    }
+}
@@ -1,4 +1,8 @@
 pub mod custom_error;
 
+pub mod cycle;
 pub mod part1;
 pub mod part2;
+
+pub const DAY: u8 = 8;
+pub const TITLE: &str = "Haunted Wasteland";
@@ -1,18 +1,22 @@
 use std::collections::HashMap;
 
+use aoc_core::{parse::finish, Solution};
+
 use crate::custom_error::AocError;
+use crate::cycle::Cycle;
 
 use nom::{
     branch::alt,
     bytes::complete::tag,
     character::complete::{self, alpha1, line_ending, multispace1},
+    combinator::eof,
     multi::{fold_many1, many1},
     sequence::{separated_pair, terminated},
     IResult, Parser,
 };
 
 #[derive(Debug)]
-enum Move {
+pub enum Move {
     Left,
     Right,
 }
@@ -38,7 +42,7 @@ fn parse_maps(input: &str) -> IResult<&str, HashMap<&str, (&str, &str)>> {
                 ),
                 tag(")"),
             ),
-            line_ending,
+            alt((line_ending, eof)),
         ),
         HashMap::new,
         |mut acc: HashMap<&str, (&str, &str)>, (key, map)| {
@@ -50,31 +54,70 @@ fn parse_maps(input: &str) -> IResult<&str, HashMap<&str, (&str, &str)>> {
     Ok((input, maps))
 }
 
+#[derive(Debug)]
+pub struct Network<'a> {
+    pub instructions: Vec<Move>,
+    pub map: HashMap<&'a str, (&'a str, &'a str)>,
+}
+
+#[tracing::instrument]
+pub fn parse(input: &str) -> miette::Result<Network<'_>, AocError> {
+    let (instructions, map) = finish(input, parse_instructions.and(parse_maps))?;
+    for node in map.values().flat_map(|(left, right)| [left, right]) {
+        if !map.contains_key(node) {
+            return Err(AocError::parse_error(
+                input,
+                node,
+                "this node is never defined",
+            ));
+        }
+    }
+    Ok(Network { instructions, map })
+}
+
+#[tracing::instrument(skip(network))]
+pub fn solve(network: &Network<'_>) -> miette::Result<String, AocError> {
+    let Network { instructions, map } = network;
+    if !map.contains_key("AAA") {
+        return Err(AocError::invalid_input("the network has no `AAA` node"));
+    }
+    let cycle = Cycle::find(
+        "AAA",
+        instructions.len(),
+        |node, instruction| match instructions[instruction] {
+            Move::Left => map[node].0,
+            Move::Right => map[node].1,
+        },
+        |node| node == "ZZZ",
+    );
+    let steps = cycle
+        .first_hit()
+        .ok_or_else(|| AocError::invalid_input("`ZZZ` can not be reached from `AAA`"))?;
+    Ok(steps.to_string())
+}
+
 #[tracing::instrument]
-#[warn(unreachable_code)]
 pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let (input, instructions) = parse_instructions(input).expect("Should be valid!");
-    let (_, map) = parse_maps(input).expect("Should be valid!");
-    let mut counter = 0;
-    let mut current_place = map.get("AAA").expect("Should exist");
-    for next_move in instructions.iter().cycle() {
-        counter += 1;
-        current_place = match next_move {
-            Move::Left => {
-                if current_place.0 == "ZZZ" {
-                    return Ok((counter).to_string());
-                }
-                map.get(current_place.0).expect("Should exist")
-            }
-            Move::Right => {
-                if current_place.1 == "ZZZ" {
-                    return Ok((counter).to_string());
-                }
-                map.get(current_place.1).expect("Should exist")
-            }
-        };
+    solve(&parse(input)?)
+}
+
+pub struct Part1;
+
+impl Solution for Part1 {
+    const DAY: u8 = crate::DAY;
+    const PART: u8 = 1;
+    const TITLE: &'static str = crate::TITLE;
+
+    type Model<'a> = Network<'a>;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Self::Model<'_>, AocError> {
+        parse(input)
+    }
+
+    fn solve(network: &Self::Model<'_>, _params: &Self::Params) -> Result<String, AocError> {
+        solve(network)
     }
-    unreachable!();
 }
 
 #[cfg(test)]
@@ -91,4 +134,20 @@ ZZZ = (ZZZ, ZZZ)";
         assert_eq!("6", process(input)?);
         Ok(())
     }
+
+    #[test]
+    fn test_parse_error() {
+        let input = "LLR
+
+AAA = (BBB, BBB)
+BBB = (AAA, ZZZ)";
+        assert!(matches!(
+            parse(input),
+            Err(AocError::ParseError {
+                line: 4,
+                column: 13,
+                ..
+            })
+        ));
+    }
 }
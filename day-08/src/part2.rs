@@ -1,18 +1,22 @@
 use std::collections::HashMap;
 
+use aoc_core::{parse::finish, Solution};
+
 use crate::custom_error::AocError;
+use crate::cycle::{first_common_hit, Cycle};
 
 use nom::{
     branch::alt,
     bytes::complete::tag,
     character::complete::{self, alphanumeric1, line_ending, multispace1},
+    combinator::eof,
     multi::{fold_many1, many1},
     sequence::{separated_pair, terminated},
     IResult, Parser,
 };
 
 #[derive(Debug)]
-enum Move {
+pub enum Move {
     Left,
     Right,
 }
@@ -38,7 +42,7 @@ fn parse_maps(input: &str) -> IResult<&str, HashMap<&str, (&str, &str)>> {
                 ),
                 tag(")"),
             ),
-            line_ending,
+            alt((line_ending, eof)),
         ),
         HashMap::new,
         |mut acc: HashMap<&str, (&str, &str)>, (key, map)| {
@@ -50,63 +54,90 @@ fn parse_maps(input: &str) -> IResult<&str, HashMap<&str, (&str, &str)>> {
     Ok((input, maps))
 }
 
+#[derive(Debug)]
+pub struct Network<'a> {
+    pub instructions: Vec<Move>,
+    pub map: HashMap<&'a str, (&'a str, &'a str)>,
+}
+
 #[tracing::instrument]
-#[warn(unreachable_code)]
-pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let (input, instructions) = parse_instructions(input).expect("Should be valid!");
-    let (_, map) = parse_maps(input).expect("Should be valid!");
-    let current_places = {
-        map.iter()
-            .filter(|(key, _)| key.ends_with('A'))
-            .map(|(_, places)| places)
-            .collect::<Vec<&(&str, &str)>>()
-    };
-    let min_steps_each_map = current_places
+pub fn parse(input: &str) -> miette::Result<Network<'_>, AocError> {
+    let (instructions, map) = finish(input, parse_instructions.and(parse_maps))?;
+    for node in map.values().flat_map(|(left, right)| [left, right]) {
+        if !map.contains_key(node) {
+            return Err(AocError::parse_error(
+                input,
+                node,
+                "this node is never defined",
+            ));
+        }
+    }
+    Ok(Network { instructions, map })
+}
+
+#[tracing::instrument(skip(network))]
+pub fn solve(network: &Network<'_>) -> miette::Result<String, AocError> {
+    let Network { instructions, map } = network;
+    let mut starts = map
+        .keys()
+        .filter(|node| node.ends_with('A'))
+        .copied()
+        .collect::<Vec<&str>>();
+    if starts.is_empty() {
+        return Err(AocError::invalid_input(
+            "the network has no node ending with `A`",
+        ));
+    }
+    starts.sort_unstable();
+    let cycles = starts
         .iter()
-        .map(|node| {
-            let mut current_node = *node;
-            instructions
-                .iter()
-                .cycle()
-                .enumerate()
-                .find_map(|(index, instruction)| match instruction {
-                    Move::Left => {
-                        if current_node.0.ends_with('Z') {
-                            return Some(index + 1);
-                        }
-                        current_node = map.get(current_node.0).expect("Should exist");
-                        None
-                    }
-                    Move::Right => {
-                        if current_node.1.ends_with('Z') {
-                            return Some(index + 1);
-                        }
-                        current_node = map.get(current_node.1).expect("Should exist");
-                        None
-                    }
-                })
-                .expect("Should exist")
+        .map(|start| {
+            let cycle = Cycle::find(
+                *start,
+                instructions.len(),
+                |node, instruction| match instructions[instruction] {
+                    Move::Left => map[node].0,
+                    Move::Right => map[node].1,
+                },
+                |node| node.ends_with('Z'),
+            );
+            match cycle.first_hit() {
+                Some(_) => Ok(cycle),
+                None => Err(AocError::invalid_input(format!(
+                    "the ghost starting at `{start}` never reaches a node ending with `Z`"
+                ))),
+            }
         })
-        .collect::<Vec<usize>>();
-    let min_cycle = lcm(&min_steps_each_map);
+        .collect::<Result<Vec<Cycle>, AocError>>()?;
+    let steps = first_common_hit(&cycles).ok_or_else(|| {
+        AocError::invalid_input("the ghosts are never on nodes ending with `Z` at the same time")
+    })?;
 
-    Ok(min_cycle.to_string())
+    Ok(steps.to_string())
 }
 
-pub fn lcm(nums: &[usize]) -> usize {
-    if nums.len() == 1 {
-        return nums[0];
-    }
-    let a = nums[0];
-    let b = lcm(&nums[1..]);
-    a * b / gcd(a, b)
+#[tracing::instrument]
+pub fn process(input: &str) -> miette::Result<String, AocError> {
+    solve(&parse(input)?)
 }
 
-fn gcd(a: usize, b: usize) -> usize {
-    if b == 0 {
-        return a;
+pub struct Part2;
+
+impl Solution for Part2 {
+    const DAY: u8 = crate::DAY;
+    const PART: u8 = 2;
+    const TITLE: &'static str = crate::TITLE;
+
+    type Model<'a> = Network<'a>;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Self::Model<'_>, AocError> {
+        parse(input)
+    }
+
+    fn solve(network: &Self::Model<'_>, _params: &Self::Params) -> Result<String, AocError> {
+        solve(network)
     }
-    gcd(b, a % b)
 }
 
 #[cfg(test)]
@@ -128,4 +159,16 @@ XXX = (XXX, XXX)";
         assert_eq!("6", process(input)?);
         Ok(())
     }
+
+    #[test]
+    fn test_never_in_sync() {
+        let input = "L
+
+11A = (11Z, 11Z)
+11Z = (11A, 11A)
+22A = (22B, 22B)
+22B = (22Z, 22Z)
+22Z = (22B, 22B)";
+        assert!(matches!(process(input), Err(AocError::InvalidInput(_))));
+    }
 }
@@ -2,3 +2,7 @@ pub mod custom_error;
 
 pub mod part1;
 pub mod part2;
+pub mod sequence;
+
+pub const DAY: u8 = 9;
+pub const TITLE: &str = "Mirage Maintenance";
@@ -1,25 +1,57 @@
+use aoc_core::Solution;
+
 use crate::custom_error::AocError;
+use crate::sequence::Sequence;
 
 #[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let result = input
+pub fn parse(input: &str) -> miette::Result<Vec<Vec<i64>>, AocError> {
+    input
         .lines()
         .map(|k| {
+            if k.trim().is_empty() {
+                return Err(AocError::parse_error(input, k, "expected a history"));
+            }
             k.split_whitespace()
-                .map(|v| v.parse::<i32>().expect("Should be valid"))
-                .collect::<Vec<i32>>()
+                .map(|v| {
+                    v.parse::<i64>()
+                        .map_err(|_| AocError::parse_error(input, v, "expected a number"))
+                })
+                .collect::<Result<Vec<i64>, AocError>>()
         })
-        .fold(0, |sum, x| {
-            sum + x.last().expect("Must exist") + vectorize(&x)
-        });
-    Ok(result.to_string())
+        .collect()
+}
+
+#[tracing::instrument(skip(histories))]
+pub fn solve(histories: &[Vec<i64>]) -> miette::Result<String, AocError> {
+    let result = histories.iter().try_fold(0_i128, |sum, history| {
+        sum.checked_add(Sequence::new(history.iter().map(|v| i128::from(*v)))?.forward(1)?)
+    });
+    result
+        .map(|result| result.to_string())
+        .ok_or_else(|| AocError::invalid_input("extrapolated values overflow"))
 }
 
-fn vectorize(slice: &[i32]) -> i32 {
-    let new_vec = slice.windows(2).map(|k| k[1] - k[0]).collect::<Vec<i32>>();
-    match new_vec.iter().all(|v| *v == 0) {
-        true => 0,
-        false => new_vec.last().expect("Should exist") + vectorize(&new_vec)
+#[tracing::instrument]
+pub fn process(input: &str) -> miette::Result<String, AocError> {
+    solve(&parse(input)?)
+}
+
+pub struct Part1;
+
+impl Solution for Part1 {
+    const DAY: u8 = crate::DAY;
+    const PART: u8 = 1;
+    const TITLE: &'static str = crate::TITLE;
+
+    type Model<'a> = Vec<Vec<i64>>;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Self::Model<'_>, AocError> {
+        parse(input)
+    }
+
+    fn solve(histories: &Self::Model<'_>, _params: &Self::Params) -> Result<String, AocError> {
+        solve(histories)
     }
 }
 
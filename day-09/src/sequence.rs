@@ -0,0 +1,169 @@
+/// The signed integers a sequence can be extrapolated in.
+pub trait Term: Copy + Eq + From<i64> {
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+    fn checked_div(self, rhs: Self) -> Option<Self>;
+}
+
+macro_rules! term {
+    ($($int:ty),*) => {
+        $(
+            impl Term for $int {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+
+                fn checked_add(self, rhs: Self) -> Option<Self> {
+                    <$int>::checked_add(self, rhs)
+                }
+
+                fn checked_sub(self, rhs: Self) -> Option<Self> {
+                    <$int>::checked_sub(self, rhs)
+                }
+
+                fn checked_mul(self, rhs: Self) -> Option<Self> {
+                    <$int>::checked_mul(self, rhs)
+                }
+
+                fn checked_div(self, rhs: Self) -> Option<Self> {
+                    <$int>::checked_div(self, rhs)
+                }
+            }
+        )*
+    };
+}
+
+term!(i64, i128);
+
+/// The polynomial through a sequence of values, kept as its Newton forward
+/// differences at the first value: `f(x) = Σ Δᵏf(0) · C(x, k)`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sequence<T> {
+    len: usize,
+    /// `Δᵏf(0)` for every `k` up to the degree
+    differences: Vec<T>,
+}
+
+impl<T: Term> Sequence<T> {
+    /// `None` when one of the differences overflows `T`.
+    pub fn new(values: impl IntoIterator<Item = T>) -> Option<Self> {
+        let mut differences = values.into_iter().collect::<Vec<T>>();
+        let len = differences.len();
+        // Level by level from the back, so every slot still holds the lower
+        // level when its neighbour needs it
+        for level in 1..len {
+            for i in (level..len).rev() {
+                differences[i] = differences[i].checked_sub(differences[i - 1])?;
+            }
+        }
+        let degree = differences
+            .iter()
+            .rposition(|difference| *difference != T::ZERO);
+        differences.truncate(degree.map_or(0, |degree| degree + 1));
+        Some(Sequence { len, differences })
+    }
+
+    /// The number of values the sequence was built from
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The degree of the lowest polynomial through every value, `None` when all
+    /// of them are zero.
+    pub fn degree(&self) -> Option<usize> {
+        self.differences.len().checked_sub(1)
+    }
+
+    /// The value at `index`, counted from the first value, which may lie on
+    /// either side of the known values. `None` when it overflows `T`.
+    pub fn at(&self, index: i64) -> Option<T> {
+        let x = T::from(index);
+        let mut binomial = T::ONE;
+        let mut value = T::ZERO;
+        for (k, difference) in self.differences.iter().enumerate() {
+            if k > 0 {
+                // C(x, k) = C(x, k - 1) · (x - k + 1) / k, always a whole number
+                let k = T::from(i64::try_from(k).ok()?);
+                binomial = binomial
+                    .checked_mul(x.checked_sub(k)?.checked_add(T::ONE)?)?
+                    .checked_div(k)?;
+            }
+            value = value.checked_add(difference.checked_mul(binomial)?)?;
+        }
+        Some(value)
+    }
+
+    /// The value `steps` after the last one
+    pub fn forward(&self, steps: u64) -> Option<T> {
+        let last = i64::try_from(self.len).ok()? - 1;
+        self.at(last.checked_add(i64::try_from(steps).ok()?)?)
+    }
+
+    /// The value `steps` before the first one
+    pub fn backward(&self, steps: u64) -> Option<T> {
+        self.at(i64::try_from(steps).ok()?.checked_neg()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        let examples = [
+            ([0, 3, 6, 9, 12, 15], 18, -3, Some(1)),
+            ([1, 3, 6, 10, 15, 21], 28, 0, Some(2)),
+            ([10, 13, 16, 21, 30, 45], 68, 5, Some(3)),
+        ];
+        for (values, next, previous, degree) in examples {
+            let sequence = Sequence::<i64>::new(values).unwrap();
+            assert_eq!(Some(next), sequence.forward(1));
+            assert_eq!(Some(previous), sequence.backward(1));
+            assert_eq!(degree, sequence.degree());
+        }
+    }
+
+    #[test]
+    fn test_many_steps() {
+        let cubes = Sequence::<i64>::new((2..7).map(|x: i64| x.pow(3) - x)).unwrap();
+        assert_eq!(Some(3), cubes.degree());
+        for x in -20_i64..20 {
+            assert_eq!(Some(x.pow(3) - x), cubes.at(x - 2));
+        }
+        assert_eq!(Some(999_900), cubes.forward(94));
+        assert_eq!(Some(-999_900), cubes.backward(102));
+    }
+
+    #[test]
+    fn test_constant_and_empty() {
+        let constant = Sequence::<i64>::new([7, 7, 7]).unwrap();
+        assert_eq!(
+            (Some(0), Some(7)),
+            (constant.degree(), constant.backward(10))
+        );
+        let zero = Sequence::<i64>::new([0, 0]).unwrap();
+        assert_eq!((None, Some(0)), (zero.degree(), zero.forward(3)));
+        let empty = Sequence::<i64>::new([]).unwrap();
+        assert!(empty.is_empty());
+        assert_eq!(Some(0), empty.at(5));
+    }
+
+    #[test]
+    fn test_overflow() {
+        assert_eq!(None, Sequence::<i64>::new([i64::MIN, i64::MAX]));
+        let steep = Sequence::<i64>::new([0, i64::MAX / 2]).unwrap();
+        assert_eq!(Some(i64::MAX - 1), steep.forward(1));
+        assert_eq!(None, steep.forward(2));
+        let steep = Sequence::<i128>::new([0, i128::from(i64::MAX / 2)]).unwrap();
+        assert_eq!(Some(i128::from(i64::MAX / 2) * 3), steep.forward(2));
+        assert_eq!(None, steep.forward(u64::MAX));
+    }
+}
@@ -5,12 +5,41 @@ fn main() {
     divan::main();
 }
 
+#[cfg(feature = "embedded-input")]
+fn input(part: u8) -> String {
+    match part {
+        1 => include_str!("../input1.txt"),
+        _ => include_str!("../input2.txt"),
+    }
+    .to_string()
+}
+
+#[cfg(not(feature = "embedded-input"))]
+fn input(part: u8) -> String {
+    aoc_core::input::load(DAY, part).unwrap()
+}
+
+#[divan::bench]
+fn part1(bencher: divan::Bencher) {
+    let input = input(1);
+    bencher.bench_local(|| part1::process(divan::black_box(&input)).unwrap());
+}
+
+#[divan::bench]
+fn part1_parse(bencher: divan::Bencher) {
+    let input = input(1);
+    bencher.bench_local(|| part1::parse(divan::black_box(&input)).unwrap());
+}
+
 #[divan::bench]
-fn part1() {
-    part1::process(divan::black_box(include_str!("../input1.txt",))).unwrap();
+fn part1_solve(bencher: divan::Bencher) {
+    let input = input(1);
+    let model = part1::parse(&input).unwrap();
+    bencher.bench_local(|| part1::solve(divan::black_box(&model)).unwrap());
 }
 
 #[divan::bench]
-fn part2() {
-    part2::process(divan::black_box(include_str!("../input2.txt",))).unwrap();
+fn part2(bencher: divan::Bencher) {
+    let input = input(2);
+    bencher.bench_local(|| part2::process(divan::black_box(&input)).unwrap());
 }
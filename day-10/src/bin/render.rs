@@ -0,0 +1,22 @@
+use day_10::{
+    part1::parse,
+    render::{Picture, Render},
+};
+use miette::Context;
+
+/// Draws the maze with its main loop, `--param svg=true` for an SVG image:
+///
+/// cargo run -p day-10 --bin render -- --input example.txt --param svg=true > maze.svg
+#[tracing::instrument]
+fn main() -> miette::Result<()> {
+    tracing_subscriber::fmt::init();
+
+    let (file, render) = aoc_core::input::from_args_with_params::<Render>(day_10::DAY, 1)?;
+    let maze = parse(&file).context("parse the maze")?;
+    let picture = Picture::new(&maze).context("find the main loop")?;
+    match render.svg {
+        true => print!("{}", picture.svg()),
+        false => print!("{}", picture.ansi()),
+    }
+    Ok(())
+}
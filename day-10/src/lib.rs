@@ -2,3 +2,7 @@ pub mod custom_error;
 
 pub mod part1;
 pub mod part2;
+pub mod render;
+
+pub const DAY: u8 = 10;
+pub const TITLE: &str = "Pipe Maze";
@@ -1,14 +1,23 @@
 use std::collections::VecDeque;
 
+use aoc_core::{parse::grid_of, Solution};
+
 use crate::custom_error::AocError;
 
+type Tile<'a> = ((&'a usize, &'a usize), &'a &'a u8);
+
 fn extract_around<'a>(
     (y, x): (usize, usize),
     vec: &'a [(usize, Vec<(usize, &'a u8)>)],
-    was_visited: &mut Vec<Vec<bool>>,
-) -> Option<Vec<((&'a usize, &'a usize), &'a &'a u8)>> {
+    start_pipe: u8,
+    was_visited: &mut [Vec<bool>],
+) -> Option<Vec<Tile<'a>>> {
     let mut res = Vec::new();
-    if [b'S', b'|', b'L', b'J'].contains(vec[y].1[x].1)
+    let pipe = match vec[y].1[x].1 {
+        b'S' => start_pipe,
+        pipe => *pipe,
+    };
+    if [b'|', b'L', b'J'].contains(&pipe)
         && y > 0
         && [b'F', b'7', b'|'].contains(vec[y - 1].1[x].1)
         && !was_visited[y - 1][x]
@@ -16,7 +25,7 @@ fn extract_around<'a>(
         res.push(extract_given((y - 1, x), vec));
         was_visited[y - 1][x] = true;
     };
-    if [b'S', b'|', b'F', b'7'].contains(vec[y].1[x].1)
+    if [b'|', b'F', b'7'].contains(&pipe)
         && y + 1 < vec.len()
         && [b'J', b'L', b'|'].contains(vec[y + 1].1[x].1)
         && !was_visited[y + 1][x]
@@ -24,7 +33,7 @@ fn extract_around<'a>(
         res.push(extract_given((y + 1, x), vec));
         was_visited[y + 1][x] = true;
     };
-    if [b'S', b'-', b'J', b'7'].contains(vec[y].1[x].1)
+    if [b'-', b'J', b'7'].contains(&pipe)
         && x > 0
         && [b'L', b'-', b'F'].contains(vec[y].1[x - 1].1)
         && !was_visited[y][x - 1]
@@ -32,7 +41,7 @@ fn extract_around<'a>(
         res.push(extract_given((y, x - 1), vec));
         was_visited[y][x - 1] = true;
     };
-    if [b'S', b'-', b'F', b'L'].contains(vec[y].1[x].1)
+    if [b'-', b'F', b'L'].contains(&pipe)
         && x + 1 < vec[0].1.len()
         && [b'J', b'7', b'-'].contains(vec[y].1[x + 1].1)
         && !was_visited[y][x + 1]
@@ -46,20 +55,92 @@ fn extract_around<'a>(
     }
 }
 
-fn extract_given<'a>(
-    (y, x): (usize, usize),
-    vec: &'a [(usize, Vec<(usize, &'a u8)>)],
-) -> ((&usize, &usize), &&'a u8) {
+fn extract_given<'a>((y, x): (usize, usize), vec: &'a [(usize, Vec<(usize, &'a u8)>)]) -> Tile<'a> {
     let x_entry = &vec[y];
     ((&x_entry.0, &x_entry.1[x].0), &x_entry.1[x].1)
 }
 
+/// The offsets of the two tiles a pipe connects, `None` for ground and `S`
+pub fn ends(pipe: u8) -> Option<[(isize, isize); 2]> {
+    match pipe {
+        b'|' => Some([(-1, 0), (1, 0)]),
+        b'-' => Some([(0, -1), (0, 1)]),
+        b'L' => Some([(-1, 0), (0, 1)]),
+        b'J' => Some([(-1, 0), (0, -1)]),
+        b'7' => Some([(1, 0), (0, -1)]),
+        b'F' => Some([(1, 0), (0, 1)]),
+        _ => None,
+    }
+}
+
+#[derive(Debug)]
+pub struct Maze<'a> {
+    pub lines: Vec<(usize, Vec<(usize, &'a u8)>)>,
+    pub starting_point: (usize, usize),
+    /// The pipe hidden under `S`, the one whose both ends connect back and
+    /// that closes the main loop
+    pub start_pipe: u8,
+}
+
+impl Maze<'_> {
+    /// The pipe on a tile, with `S` replaced by the pipe under it
+    pub fn pipe(&self, (y, x): (usize, usize)) -> u8 {
+        match self.lines[y].1[x].1 {
+            b'S' => self.start_pipe,
+            pipe => *pipe,
+        }
+    }
+
+    /// The tile `offset` away from `(y, x)`, `None` outside of the maze
+    fn neighbour(
+        &self,
+        (y, x): (usize, usize),
+        (dy, dx): (isize, isize),
+    ) -> Option<(usize, usize)> {
+        let y = y.checked_add_signed(dy).filter(|y| *y < self.lines.len())?;
+        let x = x
+            .checked_add_signed(dx)
+            .filter(|x| *x < self.lines[y].1.len())?;
+        Some((y, x))
+    }
+
+    /// The pipes whose both ends lead to a pipe pointing back at `S`
+    fn start_pipe_candidates(&self) -> Vec<u8> {
+        let points_back = |offset: (isize, isize)| {
+            self.neighbour(self.starting_point, offset)
+                .and_then(|tile| ends(self.pipe(tile)))
+                .is_some_and(|ends| ends.contains(&(-offset.0, -offset.1)))
+        };
+        b"|-LJ7F"
+            .iter()
+            .copied()
+            .filter(|pipe| ends(*pipe).is_some_and(|ends| ends.into_iter().all(points_back)))
+            .collect()
+    }
+
+    /// Whether the pipes on both tiles point at each other
+    fn connected(&self, from: (usize, usize), to: (usize, usize)) -> bool {
+        let points_at = |from, to| {
+            ends(self.pipe(from)).is_some_and(|ends| {
+                ends.into_iter()
+                    .any(|offset| self.neighbour(from, offset) == Some(to))
+            })
+        };
+        points_at(from, to) && points_at(to, from)
+    }
+}
+
 #[tracing::instrument]
-#[warn(unreachable_code)]
-pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let mut starting_point = (input.find('S').expect("Should exist"), 0);
-    let lines: Vec<(usize, Vec<(usize, &u8)>)> = input
-        .lines()
+pub fn parse(input: &str) -> miette::Result<Maze<'_>, AocError> {
+    let grid = grid_of(input, "|-LJ7F.S")?;
+    if !input.contains('S') {
+        return Err(AocError::invalid_input(
+            "the maze has no starting point `S`",
+        ));
+    }
+    let mut starting_point = (0, 0);
+    let lines: Vec<(usize, Vec<(usize, &u8)>)> = grid
+        .into_iter()
         .map(|line| {
             line.as_bytes()
                 .iter()
@@ -80,22 +161,112 @@ pub fn process(input: &str) -> miette::Result<String, AocError> {
             (idx, k)
         })
         .collect::<Vec<(usize, Vec<(usize, &u8)>)>>();
-    let mut queue: VecDeque<(u32, ((&usize, &usize), &&u8))> = VecDeque::new();
+    let mut maze = Maze {
+        lines,
+        starting_point,
+        start_pipe: b'S',
+    };
+    let candidates = maze.start_pipe_candidates();
+    maze.start_pipe = match candidates.as_slice() {
+        [] => return Err(AocError::invalid_input(
+            "the pipe under `S` can not be inferred, two of its neighbours have to connect to it",
+        )),
+        [pipe] => *pipe,
+        // More than two neighbours point at `S`, the pipe under it is the one
+        // the loop closes through. Without one `main_loop` reports the error.
+        pipes => pipes
+            .iter()
+            .copied()
+            .find(|pipe| {
+                maze.start_pipe = *pipe;
+                main_loop(&maze).is_ok()
+            })
+            .unwrap_or(pipes[0]),
+    };
+    Ok(maze)
+}
+
+/// The main loop in walking order, starting at `S`. The BFS reaches the
+/// farthest tile from both sides, so the predecessors of that tile and of its
+/// other neighbour lead back to `S` along the two halves of the loop.
+pub fn main_loop(maze: &Maze<'_>) -> miette::Result<Vec<(usize, usize)>, AocError> {
+    let Maze {
+        lines,
+        starting_point,
+        start_pipe,
+    } = maze;
+    let mut queue: VecDeque<(u32, Tile)> = VecDeque::new();
     let mut visited_map = vec![vec![false; lines[0].1.len()]; lines.len()];
+    let mut came_from = vec![vec![None; lines[0].1.len()]; lines.len()];
+    visited_map[starting_point.0][starting_point.1] = true;
     queue.push_back((0_u32, ((&starting_point.0, &starting_point.1), &&b'S')));
-    loop {
-        let point = queue.pop_front().expect("Exists!");
-        if let Some(v) = extract_around((*point.1 .0 .0, *point.1 .0 .1), &lines, &mut visited_map)
-        {
+    let mut farthest = *starting_point;
+    while let Some(point) = queue.pop_front() {
+        let current = (*point.1 .0 .0, *point.1 .0 .1);
+        farthest = current;
+        if let Some(v) = extract_around(current, lines, *start_pipe, &mut visited_map) {
             v.into_iter().for_each(|neighbour| {
+                came_from[*neighbour.0 .0][*neighbour.0 .1] = Some(current);
                 queue.push_back((point.0 + 1, neighbour));
             })
         }
-        if queue.is_empty() {
-            return Ok(point.0.to_string());
-        }
     }
-    unreachable!()
+    let back_to_start = |from: (usize, usize)| {
+        std::iter::successors(Some(from), |(y, x)| came_from[*y][*x]).collect::<Vec<_>>()
+    };
+    let mut tiles = back_to_start(farthest);
+    tiles.reverse();
+    let other_side = ends(maze.pipe(farthest))
+        .into_iter()
+        .flatten()
+        .filter_map(|offset| maze.neighbour(farthest, offset))
+        .find(|tile| Some(*tile) != came_from[farthest.0][farthest.1]);
+    if let Some(other_side) = other_side.filter(|tile| came_from[tile.0][tile.1].is_some()) {
+        let mut rest = back_to_start(other_side);
+        rest.pop();
+        tiles.extend(rest);
+    }
+    let closed = tiles.len() > 2
+        && tiles
+            .iter()
+            .zip(tiles.iter().cycle().skip(1))
+            .all(|(from, to)| maze.connected(*from, *to));
+    match closed {
+        true => Ok(tiles),
+        false => Err(AocError::invalid_input(
+            "the pipes through `S` do not form a loop",
+        )),
+    }
+}
+
+#[tracing::instrument(skip(maze))]
+pub fn solve(maze: &Maze<'_>) -> miette::Result<String, AocError> {
+    let farthest = main_loop(maze)?.len() / 2;
+    Ok(farthest.to_string())
+}
+
+#[tracing::instrument]
+pub fn process(input: &str) -> miette::Result<String, AocError> {
+    solve(&parse(input)?)
+}
+
+pub struct Part1;
+
+impl Solution for Part1 {
+    const DAY: u8 = crate::DAY;
+    const PART: u8 = 1;
+    const TITLE: &'static str = crate::TITLE;
+
+    type Model<'a> = Maze<'a>;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Self::Model<'_>, AocError> {
+        parse(input)
+    }
+
+    fn solve(maze: &Self::Model<'_>, _params: &Self::Params) -> Result<String, AocError> {
+        solve(maze)
+    }
 }
 
 #[cfg(test)]
@@ -112,4 +283,56 @@ LJ...";
         assert_eq!("8", process(input)?);
         Ok(())
     }
+
+    #[test]
+    fn test_start_with_more_than_two_neighbours() -> miette::Result<()> {
+        // `-`, `7` and `F` all fit under `S`, only `F` closes the loop
+        let input = "......
+-S-7..
+.|.|..
+.L-J..
+......";
+        let maze = parse(input)?;
+        assert_eq!(b'F', maze.start_pipe);
+        assert_eq!("4", solve(&maze)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_main_loop() -> miette::Result<()> {
+        let maze = parse(
+            "-L|F7
+7S-7|
+L|7||
+-L-J|
+L|-JF",
+        )?;
+        assert_eq!(b'F', maze.start_pipe);
+        assert_eq!(
+            vec![
+                (1, 1),
+                (2, 1),
+                (3, 1),
+                (3, 2),
+                (3, 3),
+                (2, 3),
+                (1, 3),
+                (1, 2)
+            ],
+            main_loop(&maze)?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_no_loop() {
+        assert!(matches!(
+            process("S-7\n|.|\nL-."),
+            Err(AocError::InvalidInput(_))
+        ));
+        assert!(matches!(
+            process(".|.\n-S-\n.|."),
+            Err(AocError::InvalidInput(_))
+        ));
+    }
 }
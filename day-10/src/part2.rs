@@ -1,8 +1,54 @@
+use aoc_core::Solution;
+
 use crate::custom_error::AocError;
+use crate::part1::{self, main_loop, Maze};
+
+/// The tiles enclosed by a loop given as its tiles in walking order. The
+/// shoelace formula gives twice the area inside the polygon through their
+/// centres, Pick's theorem turns that into the whole tiles strictly inside:
+/// `A = I + B / 2 - 1` with every loop tile on the boundary.
+pub fn enclosed(tiles: &[(usize, usize)]) -> usize {
+    let twice_area = tiles
+        .iter()
+        .zip(tiles.iter().cycle().skip(1))
+        .map(|(&(y1, x1), &(y2, x2))| x1 as i64 * y2 as i64 - x2 as i64 * y1 as i64)
+        .sum::<i64>()
+        .unsigned_abs() as usize;
+    (twice_area + 2).saturating_sub(tiles.len()) / 2
+}
+
+#[tracing::instrument]
+pub fn parse(input: &str) -> miette::Result<Maze<'_>, AocError> {
+    part1::parse(input)
+}
+
+#[tracing::instrument(skip(maze))]
+pub fn solve(maze: &Maze<'_>) -> miette::Result<String, AocError> {
+    Ok(enclosed(&main_loop(maze)?).to_string())
+}
 
 #[tracing::instrument]
-pub fn process(_input: &str) -> miette::Result<String, AocError> {
-    todo!("day 01 - part 1");
+pub fn process(input: &str) -> miette::Result<String, AocError> {
+    solve(&parse(input)?)
+}
+
+pub struct Part2;
+
+impl Solution for Part2 {
+    const DAY: u8 = crate::DAY;
+    const PART: u8 = 2;
+    const TITLE: &'static str = crate::TITLE;
+
+    type Model<'a> = Maze<'a>;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Self::Model<'_>, AocError> {
+        parse(input)
+    }
+
+    fn solve(maze: &Self::Model<'_>, _params: &Self::Params) -> Result<String, AocError> {
+        solve(maze)
+    }
 }
 
 #[cfg(test)]
@@ -11,9 +57,48 @@ mod tests {
 
     #[test]
     fn test_process() -> miette::Result<()> {
-        todo!("haven't built test yet");
-        let input = "";
-        assert_eq!("", process(input)?);
+        let input = "...........
+.S-------7.
+.|F-----7|.
+.||.....||.
+.||.....||.
+.|L-7.F-J|.
+.|..|.|..|.
+.L--J.L--J.
+...........";
+        assert_eq!("4", process(input)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_squeezed_between_pipes() -> miette::Result<()> {
+        let input = ".F----7F7F7F7F-7....
+.|F--7||||||||FJ....
+.||.FJ||||||||L7....
+FJL7L7LJLJ||LJ.L-7..
+L--J.L7...LJS7F-7L7.
+....F-J..F7FJ|L7L7L7
+....L7.F7||L7|.L7L7|
+.....|FJLJ|FJ|F7|.LJ
+....FJL-7.||.||||...
+....L---J.LJ.LJLJ...";
+        assert_eq!("8", process(input)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_junk_pipes() -> miette::Result<()> {
+        let input = "FF7FSF7F7F7F7F7F---7
+L|LJ||||||||||||F--J
+FL-7LJLJ||||||LJL-77
+F--JF--7||LJLJ7F7FJ-
+L---JF-JLJ.||-FJLJJ7
+|F|F-JF---7F7-L7L|7|
+|FFJF7L7F-JF7|JL---7
+7-L-JL7||F7|L7F-7F7|
+L.L7LFJ|||||FJL7||LJ
+L7JLJL-JLJLJL--JLJ.L";
+        assert_eq!("10", process(input)?);
         Ok(())
     }
 }
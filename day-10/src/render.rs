@@ -0,0 +1,281 @@
+use std::fmt::Write;
+
+use crate::custom_error::AocError;
+use crate::part1::{ends, main_loop, Maze};
+
+aoc_core::params! {
+    /// How the `render` binary draws the maze
+    pub struct Render {
+        /// Print an SVG image instead of coloured text
+        svg: bool = false,
+    }
+}
+
+/// Where a tile lies relative to the main loop.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Place {
+    Loop,
+    Inside,
+    Outside,
+}
+
+/// The maze with every tile placed relative to the main loop, ready to be
+/// drawn. Pipes that are not part of the loop are junk and drawn dimmed.
+#[derive(Debug, Clone)]
+pub struct Picture {
+    /// The pipe on every tile, with `S` replaced by the pipe under it
+    pipes: Vec<Vec<u8>>,
+    places: Vec<Vec<Place>>,
+    /// The loop in walking order, starting at `S`
+    main_loop: Vec<(usize, usize)>,
+}
+
+/// The box-drawing character for a pipe, `·` for ground
+pub fn glyph(pipe: u8) -> char {
+    match pipe {
+        b'|' => '│',
+        b'-' => '─',
+        b'L' => '└',
+        b'J' => '┘',
+        b'7' => '┐',
+        b'F' => '┌',
+        _ => '·',
+    }
+}
+
+const CELL: usize = 10;
+const RESET: &str = "\x1b[0m";
+
+impl Picture {
+    /// Walks each row from the left and flips between outside and inside on
+    /// every loop pipe with an end pointing north, the same parity the loop
+    /// has on a line through the upper half of the row.
+    pub fn new(maze: &Maze<'_>) -> miette::Result<Self, AocError> {
+        let main_loop = main_loop(maze)?;
+        let pipes = (0..maze.lines.len())
+            .map(|y| {
+                (0..maze.lines[y].1.len())
+                    .map(|x| maze.pipe((y, x)))
+                    .collect::<Vec<u8>>()
+            })
+            .collect::<Vec<_>>();
+        let mut places = pipes
+            .iter()
+            .map(|row| vec![Place::Outside; row.len()])
+            .collect::<Vec<_>>();
+        for (y, x) in &main_loop {
+            places[*y][*x] = Place::Loop;
+        }
+        for (row, places) in pipes.iter().zip(&mut places) {
+            let mut inside = false;
+            for (pipe, place) in row.iter().zip(places.iter_mut()) {
+                match place {
+                    Place::Loop => inside ^= [b'|', b'L', b'J'].contains(pipe),
+                    _ if inside => *place = Place::Inside,
+                    _ => {}
+                }
+            }
+        }
+        Ok(Picture {
+            pipes,
+            places,
+            main_loop,
+        })
+    }
+
+    pub fn place(&self, (y, x): (usize, usize)) -> Place {
+        self.places[y][x]
+    }
+
+    pub fn count(&self, place: Place) -> usize {
+        self.places
+            .iter()
+            .flatten()
+            .filter(|p| **p == place)
+            .count()
+    }
+
+    pub fn main_loop(&self) -> &[(usize, usize)] {
+        &self.main_loop
+    }
+
+    /// The maze in box-drawing characters with ANSI colours: the loop in bold
+    /// yellow with `S` in red, inside tiles on green, ground marked `I` or `O`
+    /// and junk pipes dimmed.
+    pub fn ansi(&self) -> String {
+        let mut out = String::new();
+        for (y, (row, places)) in self.pipes.iter().zip(&self.places).enumerate() {
+            for (x, (pipe, place)) in row.iter().zip(places).enumerate() {
+                let (style, glyph) = match place {
+                    Place::Loop if (y, x) == self.main_loop[0] => ("\x1b[1;91m", glyph(*pipe)),
+                    Place::Loop => ("\x1b[1;93m", glyph(*pipe)),
+                    Place::Inside if *pipe == b'.' => ("\x1b[1;30;42m", 'I'),
+                    Place::Inside => ("\x1b[2;42m", glyph(*pipe)),
+                    Place::Outside if *pipe == b'.' => ("\x1b[2;34m", 'O'),
+                    Place::Outside => ("\x1b[2m", glyph(*pipe)),
+                };
+                out.push_str(style);
+                out.push(glyph);
+                out.push_str(RESET);
+            }
+            out.push('\n');
+        }
+        out
+    }
+
+    /// The maze as an SVG image with one `CELL` sized square per tile: inside
+    /// and outside tiles filled, junk pipes in grey, the loop as one path
+    /// through the tile centres and a dot on `S`.
+    pub fn svg(&self) -> String {
+        let height = self.pipes.len() * CELL;
+        let width = self.pipes.first().map_or(0, |row| row.len()) * CELL;
+        let mut out = String::new();
+        let _ = writeln!(
+            out,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}">"#
+        );
+        out.push_str(
+            "<style>.inside{fill:#9be39b}.outside{fill:#eef1f6}\
+             .junk{stroke:#b0b4bb;stroke-width:1.5;fill:none}\
+             .loop{stroke:#d68a00;stroke-width:3;fill:none;stroke-linejoin:round}\
+             .start{fill:#d62828}</style>\n",
+        );
+        // One rectangle per run of inside or outside tiles in a row
+        for (y, places) in self.places.iter().enumerate() {
+            for run in places
+                .iter()
+                .enumerate()
+                .collect::<Vec<_>>()
+                .chunk_by(|(_, a), (_, b)| a == b)
+            {
+                let (x, place) = run[0];
+                let class = match place {
+                    Place::Loop => continue,
+                    Place::Inside => "inside",
+                    Place::Outside => "outside",
+                };
+                let _ = writeln!(
+                    out,
+                    r#"<rect class="{class}" x="{}" y="{}" width="{}" height="{CELL}"/>"#,
+                    x * CELL,
+                    y * CELL,
+                    run.len() * CELL
+                );
+            }
+        }
+        let centre = |(y, x): (usize, usize)| (x * CELL + CELL / 2, y * CELL + CELL / 2);
+        let mut junk = String::new();
+        for (y, row) in self.pipes.iter().enumerate() {
+            for (x, pipe) in row.iter().enumerate() {
+                if self.places[y][x] == Place::Loop {
+                    continue;
+                }
+                let (cx, cy) = centre((y, x));
+                for (dy, dx) in ends(*pipe).into_iter().flatten() {
+                    let half = (CELL / 2) as isize;
+                    let _ = write!(junk, "M{cx} {cy}l{} {}", dx * half, dy * half);
+                }
+            }
+        }
+        if !junk.is_empty() {
+            let _ = writeln!(out, r#"<path class="junk" d="{junk}"/>"#);
+        }
+        let path = self
+            .main_loop
+            .iter()
+            .enumerate()
+            .map(|(i, tile)| {
+                let (cx, cy) = centre(*tile);
+                format!("{}{cx} {cy}", if i == 0 { 'M' } else { 'L' })
+            })
+            .collect::<String>();
+        let _ = writeln!(out, r#"<path class="loop" d="{path}Z"/>"#);
+        let (cx, cy) = centre(self.main_loop[0]);
+        let _ = writeln!(
+            out,
+            r#"<circle class="start" cx="{cx}" cy="{cy}" r="{}"/>"#,
+            CELL / 3
+        );
+        out.push_str("</svg>\n");
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::part1::parse;
+    use crate::part2::enclosed;
+
+    const INPUT: &str = "..........
+.S------7.
+.|F----7|.
+.||OOOO||.
+.||OOOO||.
+.|L-7F-J|.
+.|II||II|.
+.L--JL--J.
+..........";
+
+    #[test]
+    fn test_places() -> miette::Result<()> {
+        let picture = Picture::new(&parse(&INPUT.replace(['O', 'I'], "."))?)?;
+        assert_eq!(Place::Loop, picture.place((1, 1)));
+        assert_eq!(Place::Outside, picture.place((3, 3)));
+        assert_eq!(Place::Inside, picture.place((6, 2)));
+        assert_eq!(4, picture.count(Place::Inside));
+        assert_eq!(picture.main_loop().len(), picture.count(Place::Loop));
+        Ok(())
+    }
+
+    #[test]
+    fn test_parity_agrees_with_pick() -> miette::Result<()> {
+        let input = "FF7FSF7F7F7F7F7F---7
+L|LJ||||||||||||F--J
+FL-7LJLJ||||||LJL-77
+F--JF--7||LJLJ7F7FJ-
+L---JF-JLJ.||-FJLJJ7
+|F|F-JF---7F7-L7L|7|
+|FFJF7L7F-JF7|JL---7
+7-L-JL7||F7|L7F-7F7|
+L.L7LFJ|||||FJL7||LJ
+L7JLJL-JLJLJL--JLJ.L";
+        let picture = Picture::new(&parse(input)?)?;
+        assert_eq!(10, picture.count(Place::Inside));
+        assert_eq!(enclosed(picture.main_loop()), picture.count(Place::Inside));
+        Ok(())
+    }
+
+    #[test]
+    fn test_ansi() -> miette::Result<()> {
+        let picture = Picture::new(&parse(&INPUT.replace(['O', 'I'], "."))?)?;
+        let plain = picture
+            .ansi()
+            .split('\x1b')
+            .map(|part| part.split_once('m').map_or(part, |(_, text)| text))
+            .collect::<String>();
+        let expected = "OOOOOOOOOO
+O┌──────┐O
+O│┌────┐│O
+O││OOOO││O
+O││OOOO││O
+O│└─┐┌─┘│O
+O│II││II│O
+O└──┘└──┘O
+OOOOOOOOOO
+";
+        assert_eq!(expected, plain);
+        Ok(())
+    }
+
+    #[test]
+    fn test_svg() -> miette::Result<()> {
+        let picture = Picture::new(&parse(&INPUT.replace(['O', 'I'], "."))?)?;
+        let svg = picture.svg();
+        assert!(svg.starts_with("<svg") && svg.ends_with("</svg>\n"));
+        assert!(svg.contains(r#"width="100" height="90""#));
+        assert!(svg.contains(r#"<path class="loop" d="M15 15L"#));
+        assert_eq!(2, svg.matches(r#"class="inside""#).count());
+        Ok(())
+    }
+}
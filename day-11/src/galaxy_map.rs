@@ -0,0 +1,151 @@
+use aoc_core::parse::grid_of;
+
+use crate::custom_error::AocError;
+
+/// The galaxies of an image, kept as two sorted lists of coordinates. The
+/// distance between two galaxies is the sum of a distance per axis, so both
+/// axes are expanded and summed on their own.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GalaxyMap {
+    rows: Vec<u64>,
+    columns: Vec<u64>,
+}
+
+impl GalaxyMap {
+    pub fn parse(input: &str) -> Result<Self, AocError> {
+        let grid = grid_of(input, ".#")?;
+        let (mut rows, mut columns) = (Vec::new(), Vec::new());
+        for (y, line) in grid.into_iter().enumerate() {
+            for (x, _) in line.match_indices('#') {
+                rows.push(y as u64);
+                columns.push(x as u64);
+            }
+        }
+        columns.sort_unstable();
+        Ok(GalaxyMap { rows, columns })
+    }
+
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// The sum of the distances between every pair of galaxies once every
+    /// empty row and column is replaced by `factor` of them, `None` when it
+    /// does not fit in a `u64`.
+    pub fn distance_sum(&self, factor: u64) -> Option<u64> {
+        axis_sum(&self.rows, factor)?.checked_add(axis_sum(&self.columns, factor)?)
+    }
+}
+
+/// The pairwise distances of sorted coordinates after expansion. With the
+/// expanded coordinates `e_0 <= e_1 <= ...` every `e_i` is subtracted once for
+/// every galaxy after it and added once for every galaxy before it.
+fn axis_sum(sorted: &[u64], factor: u64) -> Option<u64> {
+    let (mut expanded, mut prefix, mut sum) = (0_u64, 0_u64, 0_u64);
+    for (i, coordinate) in sorted.iter().enumerate() {
+        // Every line before the first galaxy is empty, between two galaxies
+        // all but the line of the later one are
+        let gap = match i.checked_sub(1).map(|previous| sorted[previous]) {
+            None => coordinate.checked_mul(factor)?,
+            Some(previous) if previous == *coordinate => 0,
+            Some(previous) => (coordinate - previous - 1)
+                .checked_mul(factor)?
+                .checked_add(1)?,
+        };
+        expanded = expanded.checked_add(gap)?;
+        sum = sum.checked_add(expanded.checked_mul(i as u64)? - prefix)?;
+        prefix = prefix.checked_add(expanded)?;
+    }
+    Some(sum)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "...#......
+.......#..
+#.........
+..........
+......#...
+.#........
+.........#
+..........
+.......#..
+#...#.....";
+
+    /// Expands the image cell by cell and adds up every pair
+    fn brute_force(input: &str, factor: usize) -> u64 {
+        let grid = input
+            .lines()
+            .map(|line| line.as_bytes())
+            .collect::<Vec<_>>();
+        let empty_row = |y: usize| !grid[y].contains(&b'#');
+        let empty_column = |x: usize| grid.iter().all(|row| row[x] != b'#');
+        let mut galaxies = Vec::new();
+        for (y, row) in grid.iter().enumerate() {
+            for (x, cell) in row.iter().enumerate() {
+                if *cell == b'#' {
+                    let expand = |empty: usize, at: usize| at + empty * (factor - 1);
+                    galaxies.push((
+                        expand((0..y).filter(|y| empty_row(*y)).count(), y),
+                        expand((0..x).filter(|x| empty_column(*x)).count(), x),
+                    ));
+                }
+            }
+        }
+        let mut sum = 0;
+        for (i, a) in galaxies.iter().enumerate() {
+            for b in &galaxies[i + 1..] {
+                sum += (a.0.abs_diff(b.0) + a.1.abs_diff(b.1)) as u64;
+            }
+        }
+        sum
+    }
+
+    #[test]
+    fn test_examples() -> miette::Result<()> {
+        let map = GalaxyMap::parse(INPUT)?;
+        assert_eq!(9, map.len());
+        assert_eq!(Some(374), map.distance_sum(2));
+        assert_eq!(Some(1030), map.distance_sum(10));
+        assert_eq!(Some(8410), map.distance_sum(100));
+        Ok(())
+    }
+
+    #[test]
+    fn test_against_brute_force() -> miette::Result<()> {
+        let inputs = [INPUT, "#.#\n...\n#..\n..#", "..\n.#\n", "##\n##", "..\n.."];
+        for input in inputs {
+            let map = GalaxyMap::parse(input)?;
+            for factor in [1, 2, 3, 1000] {
+                assert_eq!(
+                    Some(brute_force(input, factor)),
+                    map.distance_sum(factor as u64),
+                    "{input:?} with factor {factor}"
+                );
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_many_galaxies() -> miette::Result<()> {
+        // 300 * 300 galaxies, every other row and column empty
+        let row = "#.".repeat(300);
+        let empty = ".".repeat(600);
+        let input = [row.as_str(), empty.as_str()].repeat(300).join("\n");
+        let map = GalaxyMap::parse(&input)?;
+        assert_eq!(90_000, map.len());
+        // Per axis 300 groups of 300 galaxies, group `g` ends up at `g * (1 + factor)`
+        // and the distances between groups add up to `C(301, 3)` times that
+        let axis = |factor: u64| 4_499_950 * 300 * 300 * (1 + factor);
+        assert_eq!(Some(2 * axis(1_000_000)), map.distance_sum(1_000_000));
+        assert_eq!(None, map.distance_sum(u64::MAX));
+        Ok(())
+    }
+}
@@ -1,4 +1,16 @@
 pub mod custom_error;
 
+pub mod galaxy_map;
 pub mod part1;
 pub mod part2;
+
+pub const DAY: u8 = 11;
+pub const TITLE: &str = "Cosmic Expansion";
+
+aoc_core::params! {
+    /// How much larger the empty rows and columns of the image really are
+    pub struct Expansion {
+        /// Every empty row and column is replaced by this many
+        factor: usize = 1_000_000,
+    }
+}
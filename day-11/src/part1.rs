@@ -1,107 +1,42 @@
-use std::collections::HashSet;
+use aoc_core::Solution;
 
-use crate::custom_error::AocError;
+use crate::{custom_error::AocError, galaxy_map::GalaxyMap};
 
-#[derive(Debug)]
-enum RowSpaced {
-    NotSpaced,
-    Row,
+#[tracing::instrument]
+pub fn parse(input: &str) -> miette::Result<GalaxyMap, AocError> {
+    GalaxyMap::parse(input)
 }
-#[derive(Debug, PartialEq)]
-enum Entry {
-    Dot,
-    ColumnSpacedDot,
-    RowSpacedDot,
-    ColumnRowSpacedDot,
-    Galaxy,
+
+#[tracing::instrument(skip(galaxies))]
+pub fn solve(galaxies: &GalaxyMap) -> miette::Result<String, AocError> {
+    galaxies
+        .distance_sum(2)
+        .map(|sum| sum.to_string())
+        .ok_or_else(|| AocError::invalid_input("the sum of the distances overflows"))
 }
 
-fn distances_from_source(space: Vec<Vec<Entry>>) -> Vec<(u32, u32)> {
-    let mut res = Vec::new();
-    let mut additional_down_steps = 0_u32;
-    space
-        .into_iter()
-        .enumerate()
-        .for_each(|(idx_y, row_entries)| {
-            let mut additional_right_steps = 0_u32;
-            let mut added_down_step = false;
-            row_entries
-                .into_iter()
-                .enumerate()
-                .for_each(|(idx_x, entry)| match entry {
-                    Entry::ColumnSpacedDot => additional_right_steps += 1,
-                    Entry::RowSpacedDot => {
-                        if !added_down_step {
-                            additional_down_steps += 1;
-                            added_down_step = true;
-                        }
-                    }
-                    Entry::Galaxy => res.push((
-                        idx_x as u32 + additional_right_steps,
-                        idx_y as u32 + additional_down_steps,
-                    )),
-                    _ => {}
-                })
-        });
-    res
+#[tracing::instrument]
+pub fn process(input: &str) -> miette::Result<String, AocError> {
+    solve(&parse(input)?)
 }
 
-fn parse_input(input: &str) -> Vec<Vec<Entry>> {
-    let mut filled_columns = HashSet::new();
-    let first_translation = input
-        .lines()
-        .map(|k| {
-            let mut passed_galaxy = false;
-            let entries = k
-                .as_bytes()
-                .iter()
-                .enumerate()
-                .map(|(idx, k)| match k {
-                    b'.' => Entry::Dot,
-                    _ => {
-                        passed_galaxy = true;
-                        filled_columns.insert(idx);
-                        Entry::Galaxy
-                    }
-                })
-                .collect::<Vec<Entry>>();
-            let spaced = match passed_galaxy {
-                true => RowSpaced::NotSpaced,
-                false => RowSpaced::Row,
-            };
-            (spaced, entries)
-        })
-        .collect::<Vec<(RowSpaced, Vec<Entry>)>>();
+pub struct Part1;
 
-    first_translation
-        .into_iter()
-        .map(|(rowspaced, k)| {
-            k.into_iter()
-                .enumerate()
-                .map(
-                    |(idx, v)| match (&rowspaced, filled_columns.contains(&idx), &v) {
-                        (RowSpaced::NotSpaced, false, Entry::Dot) => Entry::ColumnSpacedDot,
-                        (RowSpaced::Row, false, Entry::Dot) => Entry::ColumnRowSpacedDot,
-                        (RowSpaced::Row, true, Entry::Dot) => Entry::RowSpacedDot,
-                        _ => v,
-                    },
-                )
-                .collect::<Vec<Entry>>()
-        })
-        .collect::<Vec<Vec<Entry>>>()
-}
+impl Solution for Part1 {
+    const DAY: u8 = crate::DAY;
+    const PART: u8 = 1;
+    const TITLE: &'static str = crate::TITLE;
 
-#[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let translation = parse_input(input);
-    let mut res = 0;
-    let dist_from_source = distances_from_source(translation);
-    for (i, from) in dist_from_source.iter().enumerate() {
-        for to in dist_from_source.iter().skip(i) {
-            res += to.0.abs_diff(from.0) + to.1.abs_diff(from.1)
-        }
+    type Model<'a> = GalaxyMap;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Self::Model<'_>, AocError> {
+        parse(input)
+    }
+
+    fn solve(galaxies: &Self::Model<'_>, _params: &Self::Params) -> Result<String, AocError> {
+        solve(galaxies)
     }
-    Ok(res.to_string())
 }
 
 #[cfg(test)]
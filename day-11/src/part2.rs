@@ -1,118 +1,55 @@
-use std::collections::HashSet;
+use aoc_core::Solution;
 
-use crate::custom_error::AocError;
+use crate::{custom_error::AocError, galaxy_map::GalaxyMap, Expansion};
 
-const DIST: usize = 999999;
-
-#[derive(Debug)]
-enum RowSpaced {
-    NotSpaced,
-    Row,
-}
-#[derive(Debug, PartialEq)]
-enum Entry {
-    Dot,
-    ColumnSpacedDot,
-    RowSpacedDot,
-    ColumnRowSpacedDot,
-    Galaxy,
+#[tracing::instrument]
+pub fn parse(input: &str) -> miette::Result<GalaxyMap, AocError> {
+    GalaxyMap::parse(input)
 }
 
-fn distances_from_source(space: Vec<Vec<Entry>>) -> Vec<(usize, usize)> {
-    let mut res = Vec::new();
-    let mut additional_down_steps = 0_usize;
-    space
-        .into_iter()
-        .enumerate()
-        .for_each(|(idx_y, row_entries)| {
-            let mut additional_right_steps = 0_usize;
-            let mut added_down_step = false;
-            row_entries
-                .into_iter()
-                .enumerate()
-                .for_each(|(idx_x, entry)| match entry {
-                    Entry::ColumnSpacedDot => additional_right_steps += 1,
-                    Entry::RowSpacedDot => {
-                        if !added_down_step {
-                            additional_down_steps += 1;
-                            added_down_step = true;
-                        }
-                    }
-                    Entry::Galaxy => res.push((
-                        idx_x + additional_right_steps * DIST,
-                        idx_y + additional_down_steps * DIST,
-                    )),
-                    _ => {}
-                })
+#[tracing::instrument(skip(galaxies))]
+pub fn solve(galaxies: &GalaxyMap, expansion: &Expansion) -> miette::Result<String, AocError> {
+    if expansion.factor == 0 {
+        return Err(AocError::InvalidParam {
+            name: "factor".to_string(),
+            message: "an empty row can not shrink to nothing".to_string(),
         });
-    res
+    }
+    galaxies
+        .distance_sum(expansion.factor as u64)
+        .map(|sum| sum.to_string())
+        .ok_or_else(|| AocError::invalid_input("the sum of the distances overflows"))
 }
 
-fn parse_input(input: &str) -> Vec<Vec<Entry>> {
-    let mut filled_columns = HashSet::new();
-    let first_translation = input
-        .lines()
-        .map(|k| {
-            let mut passed_galaxy = false;
-            let entries = k
-                .as_bytes()
-                .iter()
-                .enumerate()
-                .map(|(idx, k)| match k {
-                    b'.' => Entry::Dot,
-                    _ => {
-                        passed_galaxy = true;
-                        filled_columns.insert(idx);
-                        Entry::Galaxy
-                    }
-                })
-                .collect::<Vec<Entry>>();
-            let spaced = match passed_galaxy {
-                true => RowSpaced::NotSpaced,
-                false => RowSpaced::Row,
-            };
-            (spaced, entries)
-        })
-        .collect::<Vec<(RowSpaced, Vec<Entry>)>>();
-
-    first_translation
-        .into_iter()
-        .map(|(rowspaced, k)| {
-            k.into_iter()
-                .enumerate()
-                .map(
-                    |(idx, v)| match (&rowspaced, filled_columns.contains(&idx), &v) {
-                        (RowSpaced::NotSpaced, false, Entry::Dot) => Entry::ColumnSpacedDot,
-                        (RowSpaced::Row, false, Entry::Dot) => Entry::ColumnRowSpacedDot,
-                        (RowSpaced::Row, true, Entry::Dot) => Entry::RowSpacedDot,
-                        _ => v,
-                    },
-                )
-                .collect::<Vec<Entry>>()
-        })
-        .collect::<Vec<Vec<Entry>>>()
+#[tracing::instrument]
+pub fn process(input: &str, expansion: &Expansion) -> miette::Result<String, AocError> {
+    solve(&parse(input)?, expansion)
 }
 
-#[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let translation = parse_input(input);
-    let mut res = 0;
-    let dist_from_source = distances_from_source(translation);
-    for (i, from) in dist_from_source.iter().enumerate() {
-        for to in dist_from_source.iter().skip(i) {
-            res += to.0.abs_diff(from.0) + to.1.abs_diff(from.1)
-        }
+pub struct Part2;
+
+impl Solution for Part2 {
+    const DAY: u8 = crate::DAY;
+    const PART: u8 = 2;
+    const TITLE: &'static str = crate::TITLE;
+
+    type Model<'a> = GalaxyMap;
+    type Params = Expansion;
+
+    fn parse(input: &str) -> Result<Self::Model<'_>, AocError> {
+        parse(input)
+    }
+
+    fn solve(galaxies: &Self::Model<'_>, params: &Self::Params) -> Result<String, AocError> {
+        solve(galaxies, params)
     }
-    Ok(res.to_string())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_process() -> miette::Result<()> {
-        let input = "...#......
+    const INPUT: &str = "...#......
 .......#..
 #.........
 ..........
@@ -122,7 +59,18 @@ mod tests {
 ..........
 .......#..
 #...#.....";
-        assert_eq!("82000210", process(input)?);
+
+    #[test]
+    fn test_process() -> miette::Result<()> {
+        assert_eq!("82000210", process(INPUT, &Expansion::default())?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_process_smaller_expansion() -> miette::Result<()> {
+        assert_eq!("374", process(INPUT, &Expansion { factor: 2 })?);
+        assert_eq!("1030", process(INPUT, &Expansion { factor: 10 })?);
+        assert_eq!("8410", process(INPUT, &Expansion { factor: 100 })?);
         Ok(())
     }
 }
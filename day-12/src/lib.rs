@@ -2,3 +2,15 @@ pub mod custom_error;
 
 pub mod part1;
 pub mod part2;
+pub mod springs;
+
+pub const DAY: u8 = 12;
+pub const TITLE: &str = "Hot Springs";
+
+aoc_core::params! {
+    /// How far the condition records of part 2 are unfolded
+    pub struct Unfold {
+        /// Copies of every record, the springs joined by `?`
+        copies: usize = 5,
+    }
+}
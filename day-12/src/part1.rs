@@ -1,129 +1,47 @@
+use aoc_core::{parse::finish, Solution};
+
 use crate::custom_error::AocError;
+use crate::springs::{parse_line, Puzzle};
 
-use nom::{
-    bytes::complete::{is_a, tag},
-    character::complete::{self, space1},
-    multi::separated_list1,
-    sequence::separated_pair,
-    IResult, Parser,
-};
+use nom::{character::complete::line_ending, multi::separated_list1};
 
-#[derive(Debug, PartialEq)]
-enum LineCharacter {
-    Questionmark,
-    Dot,
-    Hashtag,
-}
-#[derive(Debug)]
-struct Puzzle {
-    spring: Vec<LineCharacter>,
-    batches: Vec<u32>,
+#[tracing::instrument]
+pub fn parse(input: &str) -> miette::Result<Vec<Puzzle>, AocError> {
+    finish(input, separated_list1(line_ending, parse_line))
 }
 
-fn parse_line(input: &str) -> IResult<&str, Puzzle> {
-    let (input, (line, batches)) = separated_pair(
-        is_a("#.?").map(|s: &str| s.trim_end_matches('.')),
-        space1,
-        separated_list1(tag(","), complete::u32),
-    )
-    .parse(input)?;
-    let spring = line
-        .chars()
-        .map(|ch| match ch {
-            '#' => LineCharacter::Hashtag,
-            '.' => LineCharacter::Dot,
-            _ => LineCharacter::Questionmark,
-        })
-        .collect::<Vec<LineCharacter>>();
-    Ok((input, Puzzle { spring, batches }))
+#[tracing::instrument(skip(puzzles))]
+pub fn solve(puzzles: &[Puzzle]) -> miette::Result<String, AocError> {
+    let result = puzzles
+        .iter()
+        .try_fold(0_u64, |sum, puzzle| sum.checked_add(puzzle.arrangements()?));
+    result
+        .map(|result| result.to_string())
+        .ok_or_else(|| AocError::invalid_input("the number of arrangements overflows"))
 }
 
-fn is_valid_permutatuion(puzzle: &Vec<LineCharacter>, goal_batches: &[u32]) -> bool {
-    let mut counter = 0_u32;
-    let mut batches_iter = goal_batches.iter();
-    for i in puzzle {
-        match *i {
-            LineCharacter::Hashtag => {
-                counter += 1;
-            }
-            _ => {
-                if counter > 0 {
-                    match batches_iter.next() {
-                        Some(v) => match *v == counter {
-                            true => counter = 0,
-                            false => return false,
-                        },
-                        None => return false,
-                    }
-                }
-            }
-        }
-    }
-    match counter.cmp(&0) {
-        std::cmp::Ordering::Greater => match batches_iter.next() {
-            Some(v) => *v == counter && batches_iter.next().is_none(),
-            None => false,
-        },
-        _ => batches_iter.next().is_none(),
-    }
+#[tracing::instrument]
+pub fn process(input: &str) -> miette::Result<String, AocError> {
+    solve(&parse(input)?)
 }
 
-fn generate_all_permutations(
-    remaining_puzzle: &[LineCharacter],
-    current: &mut Vec<LineCharacter>,
-    goal_batches: &Vec<u32>,
-    result: &mut u32,
-) {
-    if remaining_puzzle.is_empty() {
-        if is_valid_permutatuion(current, goal_batches) {
-            *result += 1;
-        }
-        return;
-    }
-
-    match remaining_puzzle[0] {
-        LineCharacter::Questionmark => {
-            current.push(LineCharacter::Hashtag);
-            generate_all_permutations(&remaining_puzzle[1..], current, goal_batches, result);
-            current.pop();
-            current.push(LineCharacter::Dot);
-            generate_all_permutations(&remaining_puzzle[1..], current, goal_batches, result);
-            current.pop();
-        }
-        LineCharacter::Dot => {
-            current.push(LineCharacter::Dot);
-            generate_all_permutations(&remaining_puzzle[1..], current, goal_batches, result);
-            current.pop();
-        }
-        LineCharacter::Hashtag => {
-            current.push(LineCharacter::Hashtag);
-            generate_all_permutations(&remaining_puzzle[1..], current, goal_batches, result);
-            current.pop();
-        }
-    }
-}
+pub struct Part1;
 
-fn process_line(input: &str) -> IResult<&str, u32> {
-    let (input, puzzle) = parse_line(input)?;
-    let mut result = 0_u32;
+impl Solution for Part1 {
+    const DAY: u8 = crate::DAY;
+    const PART: u8 = 1;
+    const TITLE: &'static str = crate::TITLE;
 
-    generate_all_permutations(
-        &puzzle.spring,
-        &mut Vec::with_capacity(puzzle.spring.len()),
-        &puzzle.batches,
-        &mut result,
-    );
+    type Model<'a> = Vec<Puzzle>;
+    type Params = ();
 
-    Ok((input, result))
-}
+    fn parse(input: &str) -> Result<Self::Model<'_>, AocError> {
+        parse(input)
+    }
 
-#[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let result = input.lines().fold(0_u32, |acc, line| {
-        let (_, combinations) = process_line(line).expect("Should be valid");
-        acc + combinations
-    });
-    Ok(result.to_string())
+    fn solve(puzzles: &Self::Model<'_>, _params: &Self::Params) -> Result<String, AocError> {
+        solve(puzzles)
+    }
 }
 
 #[cfg(test)]
@@ -140,7 +58,7 @@ mod tests {
     #[case("????.######..#####. 1,6,5", 4)]
     #[case("?###???????? 3,2,1", 10)]
     #[test_log::test]
-    fn test_line(#[case] input: &str, #[case] output: u32) -> miette::Result<()> {
+    fn test_line(#[case] input: &str, #[case] output: u64) -> miette::Result<()> {
         assert_eq!(output.to_string(), process(input)?);
         Ok(())
     }
@@ -1,19 +1,88 @@
+use aoc_core::{parse::finish, Solution};
+
 use crate::custom_error::AocError;
+use crate::springs::{parse_line, Puzzle};
+use crate::Unfold;
+
+use nom::{character::complete::line_ending, multi::separated_list1};
+
+#[tracing::instrument]
+pub fn parse(input: &str) -> miette::Result<Vec<Puzzle>, AocError> {
+    finish(input, separated_list1(line_ending, parse_line))
+}
+
+#[tracing::instrument(skip(puzzles))]
+pub fn solve(puzzles: &[Puzzle], &Unfold { copies }: &Unfold) -> miette::Result<String, AocError> {
+    let result = puzzles.iter().try_fold(0_u64, |sum, puzzle| {
+        sum.checked_add(puzzle.unfold(copies).arrangements()?)
+    });
+    result
+        .map(|result| result.to_string())
+        .ok_or_else(|| AocError::invalid_input("the number of arrangements overflows"))
+}
 
 #[tracing::instrument]
-pub fn process(_input: &str) -> miette::Result<String, AocError> {
-    todo!("day 01 - part 1");
+pub fn process(input: &str, unfold: &Unfold) -> miette::Result<String, AocError> {
+    solve(&parse(input)?, unfold)
+}
+
+pub struct Part2;
+
+impl Solution for Part2 {
+    const DAY: u8 = crate::DAY;
+    const PART: u8 = 2;
+    const TITLE: &'static str = crate::TITLE;
+
+    type Model<'a> = Vec<Puzzle>;
+    type Params = Unfold;
+
+    fn parse(input: &str) -> Result<Self::Model<'_>, AocError> {
+        parse(input)
+    }
+
+    fn solve(puzzles: &Self::Model<'_>, unfold: &Self::Params) -> Result<String, AocError> {
+        solve(puzzles, unfold)
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use rstest::rstest;
+
     use super::*;
 
+    #[rstest]
+    #[case("???.### 1,1,3", 1)]
+    #[case(".??..??...?##. 1,1,3", 16384)]
+    #[case("?#?#?#?#?#?#?#? 1,3,1,6", 1)]
+    #[case("????.#...#... 4,1,1", 16)]
+    #[case("????.######..#####. 1,6,5", 2500)]
+    #[case("?###???????? 3,2,1", 506250)]
+    #[test_log::test]
+    fn test_line(#[case] input: &str, #[case] output: u64) -> miette::Result<()> {
+        assert_eq!(output.to_string(), process(input, &Unfold::default())?);
+        Ok(())
+    }
+
     #[test]
     fn test_process() -> miette::Result<()> {
-        todo!("haven't built test yet");
-        let input = "";
-        assert_eq!("", process(input)?);
+        let input = "???.### 1,1,3
+.??..??...?##. 1,1,3
+?#?#?#?#?#?#?#? 1,3,1,6
+????.#...#... 4,1,1
+????.######..#####. 1,6,5
+?###???????? 3,2,1";
+        assert_eq!("525152", process(input, &Unfold::default())?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_copies() -> miette::Result<()> {
+        assert_eq!("4", process(".??..??...?##. 1,1,3", &Unfold { copies: 1 })?);
+        assert_eq!(
+            "32",
+            process(".??..??...?##. 1,1,3", &Unfold { copies: 2 })?
+        );
         Ok(())
     }
 }
@@ -0,0 +1,208 @@
+use nom::{
+    bytes::complete::{is_a, tag},
+    character::complete::{self, space1},
+    multi::separated_list1,
+    sequence::separated_pair,
+    IResult, Parser,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LineCharacter {
+    Questionmark,
+    Dot,
+    Hashtag,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Puzzle {
+    pub spring: Vec<LineCharacter>,
+    pub batches: Vec<u32>,
+}
+
+pub(crate) fn parse_line(input: &str) -> IResult<&str, Puzzle> {
+    let (input, (line, batches)) = separated_pair(
+        is_a("#.?"),
+        space1,
+        separated_list1(tag(","), complete::u32),
+    )
+    .parse(input)?;
+    let spring = line
+        .chars()
+        .map(|ch| match ch {
+            '#' => LineCharacter::Hashtag,
+            '.' => LineCharacter::Dot,
+            _ => LineCharacter::Questionmark,
+        })
+        .collect::<Vec<LineCharacter>>();
+    Ok((input, Puzzle { spring, batches }))
+}
+
+impl Puzzle {
+    /// The record written out `copies` times, the springs joined by `?`.
+    pub fn unfold(&self, copies: usize) -> Puzzle {
+        let mut spring = Vec::with_capacity((self.spring.len() + 1) * copies);
+        for copy in 0..copies {
+            if copy > 0 {
+                spring.push(LineCharacter::Questionmark);
+            }
+            spring.extend_from_slice(&self.spring);
+        }
+        Puzzle {
+            spring,
+            batches: self.batches.repeat(copies),
+        }
+    }
+
+    /// Counts the ways to replace every `?` so the runs of `#` are exactly the
+    /// batches, `None` when the count does not fit in a `u64`.
+    ///
+    /// Walks the springs once, keeping how many arrangements of the springs so
+    /// far end in every state: the index of the batch being filled and the
+    /// length of the run of `#` at the end. A run finishes its batch on the
+    /// first `.` after it.
+    pub fn arrangements(&self) -> Option<u64> {
+        let batches = self
+            .batches
+            .iter()
+            .map(|batch| usize::try_from(*batch).ok())
+            .collect::<Option<Vec<usize>>>();
+        // Every batch needs its springs and one `.` before the next batch
+        let Some(batches) = batches.filter(|batches| {
+            batches.iter().all(|batch| *batch > 0)
+                && batches
+                    .iter()
+                    .try_fold(batches.len().saturating_sub(1), |needed, batch| {
+                        needed.checked_add(*batch)
+                    })
+                    .is_some_and(|needed| needed <= self.spring.len())
+        }) else {
+            return Some(0);
+        };
+        // `counts[group][run]`, `group == batches.len()` once every batch is done
+        let mut counts = batches
+            .iter()
+            .map(|batch| vec![0_u64; batch + 1])
+            .chain([vec![0_u64]])
+            .collect::<Vec<_>>();
+        let mut next = counts.clone();
+        counts[0][0] = 1;
+        for character in &self.spring {
+            for row in next.iter_mut() {
+                row.fill(0);
+            }
+            for (group, runs) in counts.iter().enumerate() {
+                for (run, count) in runs.iter().enumerate().filter(|(_, count)| **count > 0) {
+                    let mut add = |group: usize, run: usize| -> Option<()> {
+                        next[group][run] = next[group][run].checked_add(*count)?;
+                        Some(())
+                    };
+                    let batch = batches.get(group).copied();
+                    if *character != LineCharacter::Dot && batch.is_some_and(|batch| run < batch) {
+                        add(group, run + 1)?;
+                    }
+                    if *character != LineCharacter::Hashtag {
+                        match run {
+                            0 => add(group, 0)?,
+                            run if Some(run) == batch => add(group + 1, 0)?,
+                            _ => {}
+                        }
+                    }
+                }
+            }
+            std::mem::swap(&mut counts, &mut next);
+        }
+        let done = counts[batches.len()][0];
+        let last_open = match batches.last() {
+            Some(last) => counts[batches.len() - 1][*last],
+            None => 0,
+        };
+        done.checked_add(last_open)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn puzzle(line: &str) -> Puzzle {
+        parse_line(line).unwrap().1
+    }
+
+    /// Tries every assignment of the `?`
+    fn brute_force(puzzle: &Puzzle) -> u64 {
+        let unknown = puzzle
+            .spring
+            .iter()
+            .filter(|c| **c == LineCharacter::Questionmark)
+            .count();
+        (0..1_u32 << unknown)
+            .filter(|assignment| {
+                let mut bit = 0;
+                let line = puzzle
+                    .spring
+                    .iter()
+                    .map(|c| match c {
+                        LineCharacter::Questionmark => {
+                            bit += 1;
+                            if assignment >> (bit - 1) & 1 == 1 {
+                                '#'
+                            } else {
+                                '.'
+                            }
+                        }
+                        LineCharacter::Dot => '.',
+                        LineCharacter::Hashtag => '#',
+                    })
+                    .collect::<String>();
+                let runs = line
+                    .split('.')
+                    .filter(|run| !run.is_empty())
+                    .map(|run| run.len() as u32)
+                    .collect::<Vec<u32>>();
+                runs == puzzle.batches
+            })
+            .count() as u64
+    }
+
+    #[test]
+    fn test_against_brute_force() {
+        for line in [
+            "???.### 1,1,3",
+            ".??..??...?##. 1,1,3",
+            "?#?#?#?#?#?#?#? 1,3,1,6",
+            "?###???????? 3,2,1",
+            "??#??.?.???? 2,1",
+            "?????????? 1,1,1",
+            "#??.?#. 3,2",
+            "?#.# 1",
+        ] {
+            let puzzle = puzzle(line);
+            assert_eq!(Some(brute_force(&puzzle)), puzzle.arrangements(), "{line}");
+        }
+    }
+
+    #[test]
+    fn test_unfold() {
+        assert_eq!(
+            puzzle("???.###????.### 1,1,3,1,1,3"),
+            puzzle("???.### 1,1,3").unfold(2)
+        );
+        // Trailing dots keep the copies apart
+        assert_eq!(Some(2), puzzle("?.. 1").unfold(2).arrangements());
+        assert_eq!(Some(1), puzzle("# 1").unfold(2).arrangements());
+    }
+
+    #[test]
+    fn test_impossible_batches() {
+        assert_eq!(Some(0), puzzle("??? 0").arrangements());
+        assert_eq!(Some(0), puzzle("??? 4").arrangements());
+        assert_eq!(Some(0), puzzle("??? 4000000000").arrangements());
+    }
+
+    #[test]
+    fn test_overflow() {
+        // C(161, 40) ways to place 40 single springs in 200 places
+        let line = format!("{} {}", "?".repeat(200), vec!["1"; 40].join(","));
+        assert_eq!(None, puzzle(&line).arrangements());
+    }
+}
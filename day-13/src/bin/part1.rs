@@ -1,4 +1,4 @@
-use day_13::part1::process;
+use day_13::part1::{parse, solve, Smudges};
 use miette::Context;
 
 #[cfg(feature = "dhat-heap")]
@@ -13,8 +13,15 @@ fn main() -> miette::Result<()> {
     #[cfg(not(feature = "dhat-heap"))]
     tracing_subscriber::fmt::init();
 
-    let file = include_str!("../../input1.txt");
-    let result = process(file).context("process part 1")?;
+    #[cfg(feature = "embedded-input")]
+    let (file, smudges) = (
+        include_str!("../../input1.txt").to_string(),
+        Smudges::default(),
+    );
+    #[cfg(not(feature = "embedded-input"))]
+    let (file, smudges) = aoc_core::input::from_args_with_params::<Smudges>(day_13::DAY, 1)?;
+    let model = parse(&file).context("parse part 1")?;
+    let result = solve(&model, &smudges).context("solve part 1")?;
     println!("{}", result);
     Ok(())
 }
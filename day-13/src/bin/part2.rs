@@ -1,4 +1,4 @@
-use day_13::part2::process;
+use day_13::part2::{parse, solve, Smudges};
 use miette::Context;
 
 #[cfg(feature = "dhat-heap")]
@@ -13,8 +13,15 @@ fn main() -> miette::Result<()> {
     #[cfg(not(feature = "dhat-heap"))]
     tracing_subscriber::fmt::init();
 
-    let file = include_str!("../../input2.txt");
-    let result = process(file).context("process part 2")?;
+    #[cfg(feature = "embedded-input")]
+    let (file, smudges) = (
+        include_str!("../../input2.txt").to_string(),
+        Smudges::default(),
+    );
+    #[cfg(not(feature = "embedded-input"))]
+    let (file, smudges) = aoc_core::input::from_args_with_params::<Smudges>(day_13::DAY, 2)?;
+    let model = parse(&file).context("parse part 2")?;
+    let result = solve(&model, &smudges).context("solve part 2")?;
     println!("{}", result);
     Ok(())
 }
@@ -1,4 +1,8 @@
 pub mod custom_error;
 
+pub mod mirror;
 pub mod part1;
 pub mod part2;
+
+pub const DAY: u8 = 13;
+pub const TITLE: &str = "Point of Incidence";
@@ -0,0 +1,243 @@
+use aoc_core::parse::finish;
+use nom::{
+    bytes::complete::is_a, character::complete::line_ending, multi::separated_list1,
+    sequence::pair, IResult,
+};
+
+use crate::custom_error::AocError;
+
+/// Which way a line of reflection runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Axis {
+    /// Between two rows
+    Horizontal,
+    /// Between two columns
+    Vertical,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Reflection {
+    pub axis: Axis,
+    /// How many rows are above or columns left of the line
+    pub index: usize,
+    /// How many cells differ from their mirror image
+    pub smudges: u32,
+}
+
+impl Reflection {
+    /// The columns left of a vertical line, 100 times the rows above a horizontal one
+    pub fn summary(&self) -> u64 {
+        match self.axis {
+            Axis::Horizontal => 100 * self.index as u64,
+            Axis::Vertical => self.index as u64,
+        }
+    }
+}
+
+/// A row or column with one bit per `#`, in words of 64 cells.
+type Line = Vec<u64>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pattern {
+    rows: Vec<Line>,
+    columns: Vec<Line>,
+}
+
+impl Pattern {
+    /// Encodes a rectangle of `#` and `.`, every row has to be as long as the first.
+    pub fn new(rows: &[&[u8]]) -> Self {
+        let line = |len: usize| vec![0_u64; len.div_ceil(64)];
+        let width = rows.first().map_or(0, |row| row.len());
+        let mut encoded_rows = vec![line(width); rows.len()];
+        let mut columns = vec![line(rows.len()); width];
+        for (y, row) in rows.iter().enumerate() {
+            for (x, _) in row.iter().enumerate().filter(|(_, cell)| **cell == b'#') {
+                encoded_rows[y][x / 64] |= 1 << (x % 64);
+                columns[x][y / 64] |= 1 << (y % 64);
+            }
+        }
+        Pattern {
+            rows: encoded_rows,
+            columns,
+        }
+    }
+
+    /// Every line of reflection with exactly `smudges` cells that differ from
+    /// their mirror image, the horizontal ones first.
+    pub fn reflections(&self, smudges: u32) -> Vec<Reflection> {
+        let horizontal = mirrors(&self.rows, smudges).map(|index| Reflection {
+            axis: Axis::Horizontal,
+            index,
+            smudges,
+        });
+        let vertical = mirrors(&self.columns, smudges).map(|index| Reflection {
+            axis: Axis::Vertical,
+            index,
+            smudges,
+        });
+        horizontal.chain(vertical).collect()
+    }
+
+    /// The first of the [`reflections`](Self::reflections), `None` when there is none.
+    pub fn reflection(&self, smudges: u32) -> Option<Reflection> {
+        self.reflections(smudges).into_iter().next()
+    }
+}
+
+/// The indices of the lines with exactly `smudges` differences between the
+/// lines before them and their mirror images after them.
+fn mirrors(lines: &[Line], smudges: u32) -> impl Iterator<Item = usize> + '_ {
+    (1..lines.len()).filter(move |index| {
+        let mut differences = 0;
+        for (before, after) in lines[..*index].iter().rev().zip(&lines[*index..]) {
+            differences += before
+                .iter()
+                .zip(after)
+                .map(|(a, b)| (a ^ b).count_ones())
+                .sum::<u32>();
+            if differences > smudges {
+                return false;
+            }
+        }
+        differences == smudges
+    })
+}
+
+fn parse_input(input: &str) -> IResult<&str, Vec<Vec<&str>>> {
+    let (input, lava) = separated_list1(
+        pair(line_ending, line_ending),
+        separated_list1(line_ending, is_a("#.")),
+    )(input)?;
+    Ok((input, lava))
+}
+
+pub fn parse(input: &str) -> Result<Vec<Pattern>, AocError> {
+    let lavas = finish(input, parse_input)?;
+    for lava in &lavas {
+        if let Some(row) = lava.iter().find(|row| row.len() != lava[0].len()) {
+            return Err(AocError::parse_error(
+                input,
+                row,
+                format!("expected {} columns, found {}", lava[0].len(), row.len()),
+            ));
+        }
+    }
+    Ok(lavas
+        .into_iter()
+        .map(|lava| Pattern::new(&lava.into_iter().map(str::as_bytes).collect::<Vec<_>>()))
+        .collect())
+}
+
+/// The sum of the summaries of every pattern's first line of reflection with
+/// `smudges` smudges.
+pub fn summarize(patterns: &[Pattern], smudges: u32) -> Result<u64, AocError> {
+    patterns
+        .iter()
+        .enumerate()
+        .map(|(idx, pattern)| {
+            pattern
+                .reflection(smudges)
+                .map(|reflection| reflection.summary())
+                .ok_or_else(|| {
+                    AocError::invalid_input(format!(
+                        "pattern {} has no line of reflection",
+                        idx + 1
+                    ))
+                })
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "#.##..##.
+..#.##.#.
+##......#
+##......#
+..#.##.#.
+..##..##.
+#.#.##.#.
+
+#...##..#
+#....#..#
+..##..###
+#####.##.
+#####.##.
+..##..###
+#....#..#";
+
+    #[test]
+    fn test_reflections() -> miette::Result<()> {
+        let patterns = parse(INPUT)?;
+        let reflection = |axis, index, smudges| Reflection {
+            axis,
+            index,
+            smudges,
+        };
+        assert_eq!(
+            vec![reflection(Axis::Vertical, 5, 0)],
+            patterns[0].reflections(0)
+        );
+        assert_eq!(
+            vec![reflection(Axis::Horizontal, 4, 0)],
+            patterns[1].reflections(0)
+        );
+        assert_eq!(
+            vec![reflection(Axis::Horizontal, 3, 1)],
+            patterns[0].reflections(1)
+        );
+        assert_eq!(
+            vec![reflection(Axis::Horizontal, 1, 1)],
+            patterns[1].reflections(1)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_every_candidate() -> miette::Result<()> {
+        // Every line of an empty pattern is a mirror
+        let empty = &parse("...\n...")?[0];
+        let axes = empty
+            .reflections(0)
+            .into_iter()
+            .map(|reflection| (reflection.axis, reflection.index))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                (Axis::Horizontal, 1),
+                (Axis::Vertical, 1),
+                (Axis::Vertical, 2)
+            ],
+            axes
+        );
+        assert_eq!(2, parse("#..\n...")?[0].reflections(1).len());
+        assert!(parse("#.\n..")?[0].reflection(0).is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_wide_patterns() -> miette::Result<()> {
+        // One row mirrored between columns 100 and 101, far past one word
+        let row = (0..200_usize)
+            .map(|x| match x.min(199 - x) % 7 {
+                0 | 2 | 3 => '#',
+                _ => '.',
+            })
+            .collect::<String>();
+        let pattern = &parse(&row)?[0];
+        let mirror = Reflection {
+            axis: Axis::Vertical,
+            index: 100,
+            smudges: 0,
+        };
+        assert_eq!(vec![mirror], pattern.reflections(0));
+        let smudged = &parse(&format!("{}.", &row[..199]))?[0];
+        assert!(smudged.reflections(1).contains(&Reflection {
+            smudges: 1,
+            ..mirror
+        }));
+        Ok(())
+    }
+}
@@ -1,84 +1,51 @@
+use aoc_core::Solution;
+
 use crate::custom_error::AocError;
-use nom::{
-    bytes::complete::is_a, character::complete::line_ending, multi::separated_list1,
-    sequence::pair, IResult,
-};
+use crate::mirror::{self, summarize, Pattern};
 
-#[derive(Debug, Clone, PartialEq)]
-enum Character {
-    Hashtag,
-    Dot,
+aoc_core::params! {
+    /// Part 1 looks for perfect reflections
+    pub struct Smudges {
+        /// How many cells differ from their mirror image
+        smudges: u32 = 0,
+    }
 }
 
-fn parse_input(input: &str) -> IResult<&str, Vec<Vec<&str>>> {
-    let (input, lava) = separated_list1(
-        pair(line_ending, line_ending),
-        separated_list1(line_ending, is_a("#.")),
-    )(input)?;
-    Ok((input, lava))
+#[tracing::instrument]
+pub fn parse(input: &str) -> miette::Result<Vec<Pattern>, AocError> {
+    mirror::parse(input)
 }
-#[warn(unreachable_code)]
-fn calculate_single_lava(lava: Vec<&[u8]>) -> u32 {
-    let mut columns_vectors: Vec<Vec<Character>> =
-        vec![Vec::with_capacity(lava.len()).clone(); lava[0].len()];
-    let row_vectors = lava
-        .into_iter()
-        .map(|k| {
-            k.iter()
-                .enumerate()
-                .map(|(idx, v)| match v {
-                    b'.' => {
-                        columns_vectors[idx].push(Character::Dot);
-                        Character::Dot
-                    }
-                    _ => {
-                        columns_vectors[idx].push(Character::Hashtag);
-                        Character::Hashtag
-                    }
-                })
-                .collect()
-        })
-        .collect::<Vec<Vec<Character>>>();
-    'outer: for x in 1..row_vectors.len().max(columns_vectors.len()) {
-        if x < row_vectors.len() && row_vectors[x] == row_vectors[x - 1] {
-            for i in 1..row_vectors.len() - x {
-                if x > i && row_vectors[x - i - 1] != row_vectors[x + i] {
-                    // here is just a repetition of the lines 57-63
-                    // because we can miss a possible solution
-                    if x < columns_vectors.len() && columns_vectors[x] == columns_vectors[x - 1] {
-                        for j in 1..columns_vectors.len() - x {
-                            if x > j && columns_vectors[x - j - 1] != columns_vectors[x + j] {
-                                continue 'outer;
-                            }
-                        }
-                        return x as u32;
-                    }
-                    continue 'outer;
-                    //
-                    //
-                }
-            }
-            return x as u32 * 100;
-        }
-        if x < columns_vectors.len() && columns_vectors[x] == columns_vectors[x - 1] {
-            for i in 1..columns_vectors.len() - x {
-                if x > i && columns_vectors[x - i - 1] != columns_vectors[x + i] {
-                    continue 'outer;
-                }
-            }
-            return x as u32;
-        }
-    }
-    unreachable!();
+
+#[tracing::instrument(skip(patterns))]
+pub fn solve(
+    patterns: &[Pattern],
+    &Smudges { smudges }: &Smudges,
+) -> miette::Result<String, AocError> {
+    Ok(summarize(patterns, smudges)?.to_string())
 }
 
 #[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let (_, lavas) = parse_input(input).expect("Should be valid!");
-    let res = lavas.into_iter().fold(0_u32, |acc, lava| {
-        acc + calculate_single_lava(lava.into_iter().map(|s| s.as_bytes()).collect())
-    });
-    Ok(res.to_string())
+pub fn process(input: &str, smudges: &Smudges) -> miette::Result<String, AocError> {
+    solve(&parse(input)?, smudges)
+}
+
+pub struct Part1;
+
+impl Solution for Part1 {
+    const DAY: u8 = crate::DAY;
+    const PART: u8 = 1;
+    const TITLE: &'static str = crate::TITLE;
+
+    type Model<'a> = Vec<Pattern>;
+    type Params = Smudges;
+
+    fn parse(input: &str) -> Result<Self::Model<'_>, AocError> {
+        parse(input)
+    }
+
+    fn solve(patterns: &Self::Model<'_>, smudges: &Self::Params) -> Result<String, AocError> {
+        solve(patterns, smudges)
+    }
 }
 
 #[cfg(test)]
@@ -102,7 +69,16 @@ mod tests {
 #####.##.
 ..##..###
 #....#..#";
-        assert_eq!("405", process(input)?);
+        assert_eq!("405", process(input, &Smudges::default())?);
+        assert_eq!("400", process(input, &Smudges { smudges: 1 })?);
         Ok(())
     }
+
+    #[test]
+    fn test_no_reflection() {
+        assert!(matches!(
+            process("#.\n..", &Smudges::default()),
+            Err(AocError::InvalidInput(message)) if message == "pattern 1 has no line of reflection"
+        ));
+    }
 }
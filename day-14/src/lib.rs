@@ -2,3 +2,15 @@ pub mod custom_error;
 
 pub mod part1;
 pub mod part2;
+pub mod platform;
+
+pub const DAY: u8 = 14;
+pub const TITLE: &str = "Parabolic Reflector Dish";
+
+aoc_core::params! {
+    /// How long the platform gets spun
+    pub struct Cycles {
+        /// Spin cycles, each one tilts north, west, south and east
+        cycles: u64 = 1_000_000_000,
+    }
+}
@@ -1,50 +1,42 @@
+use aoc_core::Solution;
+
 use crate::custom_error::AocError;
-use nom::{
-    bytes::complete::is_a, character::complete::line_ending, multi::separated_list1, IResult,
-};
+use crate::platform::{Direction, Platform};
 
-#[derive(Debug)]
-enum Character {
-    Dot,
-    RoundedRock,
-    CubedRock,
+#[tracing::instrument]
+pub fn parse(input: &str) -> miette::Result<Platform, AocError> {
+    Platform::parse(input)
 }
 
-fn parse_input(input: &str) -> IResult<&str, Vec<Vec<Character>>> {
-    let (input, lines) = separated_list1(line_ending, is_a("#.O"))(input)?;
-    let charachter_lines = lines
-        .into_iter()
-        .map(|k| {
-            k.chars()
-                .map(|ch| match ch {
-                    'O' => Character::RoundedRock,
-                    '#' => Character::CubedRock,
-                    _ => Character::Dot,
-                })
-                .collect()
-        })
-        .collect::<Vec<Vec<Character>>>();
-    Ok((input, charachter_lines))
+#[tracing::instrument(skip(platform))]
+pub fn solve(platform: &Platform) -> miette::Result<String, AocError> {
+    let mut platform = platform.clone();
+    platform.tilt(Direction::North);
+    Ok(platform.north_load().to_string())
 }
 
 #[tracing::instrument]
 pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let (_, lines) = parse_input(input).expect("Should be valid");
-    let mut res = 0;
-    for x in 0..lines[0].len() {
-        let mut current_weight = lines.len();
-        for y in 0..lines.len() {
-            match lines[y][x] {
-                Character::CubedRock => current_weight = lines.len() - y - 1,
-                Character::RoundedRock => {
-                    res += current_weight;
-                    current_weight -= 1;
-                }
-                _ => {}
-            }
-        }
+    solve(&parse(input)?)
+}
+
+pub struct Part1;
+
+impl Solution for Part1 {
+    const DAY: u8 = crate::DAY;
+    const PART: u8 = 1;
+    const TITLE: &'static str = crate::TITLE;
+
+    type Model<'a> = Platform;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Self::Model<'_>, AocError> {
+        parse(input)
+    }
+
+    fn solve(platform: &Self::Model<'_>, _params: &Self::Params) -> Result<String, AocError> {
+        solve(platform)
     }
-    Ok(res.to_string())
 }
 
 #[cfg(test)]
@@ -0,0 +1,324 @@
+use std::fmt;
+
+use aoc_core::parse::grid_of;
+
+use crate::custom_error::AocError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    North,
+    West,
+    South,
+    East,
+}
+
+/// The platform as two bitboards, one bit per tile in rows of `stride` bits.
+///
+/// Every row ends with one extra column and the board has an extra row above
+/// and below, all of them cube rocks. Tilting shifts the rounded rocks one
+/// tile at a time by `±1` or `±stride`, and the frame stops them at the edges
+/// so no rotation is needed and the platform does not have to be square.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Platform {
+    width: usize,
+    height: usize,
+    cubes: Vec<u64>,
+    rounded: Vec<u64>,
+}
+
+impl Platform {
+    pub fn parse(input: &str) -> Result<Self, AocError> {
+        let lines = grid_of(input, "#.O")?;
+        let (width, height) = (lines[0].len(), lines.len());
+        let stride = width + 1;
+        let bits = (height + 2) * stride;
+        let words = bits.div_ceil(64);
+        let mut platform = Platform {
+            width,
+            height,
+            cubes: vec![0; words],
+            rounded: vec![0; words],
+        };
+        // The frame, and every bit past the last row so it is never empty
+        for index in (0..stride).chain((height + 1) * stride..words * 64) {
+            set(&mut platform.cubes, index);
+        }
+        for y in 0..height + 2 {
+            set(&mut platform.cubes, y * stride + width);
+        }
+        for (y, line) in lines.iter().enumerate() {
+            for (x, tile) in line.bytes().enumerate() {
+                let index = platform.index(y, x);
+                match tile {
+                    b'#' => set(&mut platform.cubes, index),
+                    b'O' => set(&mut platform.rounded, index),
+                    _ => {}
+                }
+            }
+        }
+        Ok(platform)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    fn stride(&self) -> usize {
+        self.width + 1
+    }
+
+    fn index(&self, y: usize, x: usize) -> usize {
+        (y + 1) * self.stride() + x
+    }
+
+    /// Rolls every rounded rock as far as it goes. All rocks that have a free
+    /// tile next to them move at once, until none of them can.
+    pub fn tilt(&mut self, direction: Direction) {
+        let stride = self.stride() as isize;
+        let step = match direction {
+            Direction::North => -stride,
+            Direction::West => -1,
+            Direction::South => stride,
+            Direction::East => 1,
+        };
+        let mut moving = vec![0; self.rounded.len()];
+        loop {
+            // The rocks whose next tile is free
+            let mut any = 0;
+            for (i, moving) in moving.iter_mut().enumerate() {
+                let blocked =
+                    shifted_word(&self.cubes, i, -step) | shifted_word(&self.rounded, i, -step);
+                *moving = self.rounded[i] & !blocked;
+                any |= *moving;
+            }
+            if any == 0 {
+                break;
+            }
+            for (rounded, moving) in self.rounded.iter_mut().zip(&moving) {
+                *rounded &= !moving;
+            }
+            for (i, rounded) in self.rounded.iter_mut().enumerate() {
+                *rounded |= shifted_word(&moving, i, step);
+            }
+        }
+    }
+
+    /// Tilts north, west, south and east.
+    pub fn spin(&mut self) {
+        for direction in [
+            Direction::North,
+            Direction::West,
+            Direction::South,
+            Direction::East,
+        ] {
+            self.tilt(direction);
+        }
+    }
+
+    /// The platform after `cycles` spin cycles. The spins repeat after a while,
+    /// so at most that many of them are simulated.
+    pub fn after_spins(&self, cycles: u64) -> Platform {
+        // Every spin so far, `history[i]` is the platform after `i` of them
+        let mut history = vec![self.clone()];
+        fn spun(history: &mut Vec<Platform>, spins: u64) -> usize {
+            while history.len() as u64 <= spins {
+                let mut platform = history[history.len() - 1].clone();
+                platform.spin();
+                history.push(platform);
+            }
+            spins as usize
+        }
+        let (start, length) = brent(|a, b| {
+            let (a, b) = (spun(&mut history, a), spun(&mut history, b));
+            history[a] == history[b]
+        });
+        let needed = match cycles.checked_sub(start) {
+            Some(in_cycle) => start + in_cycle % length,
+            None => cycles,
+        };
+        let needed = spun(&mut history, needed);
+        history.swap_remove(needed)
+    }
+
+    /// Every rounded rock weighs as much as its distance to the south edge.
+    pub fn north_load(&self) -> u64 {
+        let stride = self.stride();
+        (0..self.height)
+            .flat_map(|y| (0..self.width).map(move |x| (y, x)))
+            .filter(|(y, x)| get(&self.rounded, (y + 1) * stride + x))
+            .map(|(y, _)| (self.height - y) as u64)
+            .sum()
+    }
+}
+
+impl fmt::Display for Platform {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let index = self.index(y, x);
+                let tile = match (get(&self.cubes, index), get(&self.rounded, index)) {
+                    (true, _) => '#',
+                    (_, true) => 'O',
+                    _ => '.',
+                };
+                write!(f, "{tile}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+fn set(bits: &mut [u64], index: usize) {
+    bits[index / 64] |= 1 << (index % 64);
+}
+
+fn get(bits: &[u64], index: usize) -> bool {
+    bits[index / 64] >> (index % 64) & 1 == 1
+}
+
+/// Word `i` of the bits moved `by` places towards the higher indices, or
+/// towards the lower ones when `by` is negative, filling up with zeros.
+fn shifted_word(bits: &[u64], i: usize, by: isize) -> u64 {
+    let (words, rest) = (by.unsigned_abs() / 64, (by.unsigned_abs() % 64) as u32);
+    let word = |index: Option<usize>| index.and_then(|i| bits.get(i)).copied().unwrap_or(0);
+    let (near, far) = match by >= 0 {
+        true => (i.checked_sub(words), i.checked_sub(words + 1)),
+        false => (i.checked_add(words), i.checked_add(words + 1)),
+    };
+    match (rest, by >= 0) {
+        (0, _) => word(near),
+        (_, true) => word(near) << rest | word(far) >> (64 - rest),
+        (_, false) => word(near) >> rest | word(far) << (64 - rest),
+    }
+}
+
+/// Brent's cycle detection on a sequence of states `x_0, x_1, ...` that
+/// repeats eventually, `same(i, j)` tells whether `x_i == x_j`. Returns the
+/// index the cycle starts at and its length.
+pub fn brent(mut same: impl FnMut(u64, u64) -> bool) -> (u64, u64) {
+    // Find the length with a tortoise that jumps ahead at every power of two
+    let (mut power, mut length) = (1_u64, 1_u64);
+    let (mut tortoise, mut hare) = (0, 1);
+    while !same(tortoise, hare) {
+        if power == length {
+            tortoise = hare;
+            power *= 2;
+            length = 0;
+        }
+        hare += 1;
+        length += 1;
+    }
+    // The first state that comes back `length` states later starts the cycle
+    let mut start = 0;
+    while !same(start, start + length) {
+        start += 1;
+    }
+    (start, length)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "O....#....
+O.OO#....#
+.....##...
+OO.#O....O
+.O.....O#.
+O.#..O.#.#
+..O..#O..O
+.......O..
+#....###..
+#OO..#....";
+
+    #[test]
+    fn test_tilt_north() -> miette::Result<()> {
+        let mut platform = Platform::parse(INPUT)?;
+        assert_eq!(format!("{INPUT}\n"), platform.to_string());
+        platform.tilt(Direction::North);
+        assert_eq!(
+            "OOOO.#.O..
+OO..#....#
+OO..O##..O
+O..#.OO...
+........#.
+..#....#.#
+..O..#.O.O
+..O.......
+#....###..
+#....#....
+",
+            platform.to_string()
+        );
+        assert_eq!(136, platform.north_load());
+        Ok(())
+    }
+
+    #[test]
+    fn test_spin() -> miette::Result<()> {
+        let mut platform = Platform::parse(INPUT)?;
+        platform.spin();
+        assert_eq!(
+            ".....#....
+....#...O#
+...OO##...
+.OO#......
+.....OOO#.
+.O#...O#.#
+....O#....
+......OOOO
+#...O###..
+#..OO#....
+",
+            platform.to_string()
+        );
+        platform.spin();
+        platform.spin();
+        assert_eq!(platform, Platform::parse(INPUT)?.after_spins(3));
+        Ok(())
+    }
+
+    #[test]
+    fn test_rectangular() -> miette::Result<()> {
+        let mut platform = Platform::parse("O.#.O..\n..O...O\n.O..#.O")?;
+        assert_eq!((7, 3), (platform.width(), platform.height()));
+        platform.tilt(Direction::East);
+        assert_eq!(".O#...O\n.....OO\n...O#.O\n", platform.to_string());
+        platform.tilt(Direction::South);
+        assert_eq!("..#...O\n......O\n.O.O#OO\n", platform.to_string());
+        platform.tilt(Direction::West);
+        assert_eq!("..#O...\nO......\nOO..#OO\n", platform.to_string());
+        // Spinning a tall platform keeps its shape
+        let tall = Platform::parse("O.\n.#\n..\nO.\n.O")?.after_spins(1_000_000_000);
+        assert_eq!((2, 5), (tall.width(), tall.height()));
+        Ok(())
+    }
+
+    #[test]
+    fn test_brent() {
+        // 0, 1, ..., 9, then 4, 5, ..., 9 forever
+        let state = |i: u64| if i < 4 { i } else { 4 + (i - 4) % 6 };
+        assert_eq!((4, 6), brent(|a, b| state(a) == state(b)));
+        assert_eq!((0, 1), brent(|_, _| true));
+    }
+
+    #[test]
+    fn test_shifted() {
+        let bits = [0x8000_0000_0000_0001, 0x1];
+        let shifted = |bits: &[u64], by| {
+            (0..bits.len())
+                .map(|i| shifted_word(bits, i, by))
+                .collect::<Vec<u64>>()
+        };
+        assert_eq!(vec![0x2, 0x3], shifted(&bits, 1));
+        assert_eq!(vec![0xC000_0000_0000_0000, 0x0], shifted(&bits, -1));
+        assert_eq!(vec![0x0, 0x8000_0000_0000_0001], shifted(&bits, 64));
+        assert_eq!(vec![0x1, 0x0], shifted(&bits, -64));
+        assert_eq!(vec![0x0, 0x0], shifted(&bits, 200));
+    }
+}
@@ -0,0 +1,22 @@
+use day_15::lens_boxes::{parse, replay};
+use miette::Context;
+
+/// Prints the non-empty boxes after every step of the sequence, like the
+/// puzzle statement does for the example:
+///
+/// cargo run -p day-15 --bin replay -- --input example.txt
+#[tracing::instrument]
+fn main() -> miette::Result<()> {
+    tracing_subscriber::fmt::init();
+
+    let file = aoc_core::input::from_args(day_15::DAY, 2)?;
+    let operations = parse(&file).context("parse the initialization sequence")?;
+    for (step, (operation, snapshot)) in replay(&operations).enumerate() {
+        if step > 0 {
+            println!();
+        }
+        println!("After \"{operation}\":");
+        print!("{snapshot}");
+    }
+    Ok(())
+}
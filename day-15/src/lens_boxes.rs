@@ -0,0 +1,337 @@
+use std::fmt;
+
+use crate::custom_error::AocError;
+
+/// The HASH algorithm: add each byte, multiply by 17, keep the remainder of 256.
+pub fn hash(string: &str) -> u8 {
+    string
+        .bytes()
+        .fold(0, |acc, byte| acc.wrapping_add(byte).wrapping_mul(17))
+}
+
+/// One step of the initialization sequence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operation<'a> {
+    /// `label=N` puts a lens with focal length `N` into the label's box
+    Insert { label: &'a str, focal_length: u8 },
+    /// `label-` takes the lens with the label out of its box
+    Remove { label: &'a str },
+}
+
+impl<'a> Operation<'a> {
+    pub fn label(&self) -> &'a str {
+        match self {
+            Operation::Insert { label, .. } | Operation::Remove { label } => label,
+        }
+    }
+
+    /// The box the label belongs in
+    pub fn target(&self) -> usize {
+        hash(self.label()) as usize
+    }
+}
+
+impl fmt::Display for Operation<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Operation::Insert {
+                label,
+                focal_length,
+            } => write!(f, "{label}={focal_length}"),
+            Operation::Remove { label } => write!(f, "{label}-"),
+        }
+    }
+}
+
+/// Parses the comma separated initialization sequence. Every step needs a
+/// label and lenses have a focal length from 1 to 9.
+pub fn parse(input: &str) -> Result<Vec<Operation<'_>>, AocError> {
+    input
+        .trim_end()
+        .split(',')
+        .map(|word| {
+            let operation = match word.strip_suffix('-') {
+                Some(label) => Operation::Remove { label },
+                None => {
+                    let (label, focal_length) = word.split_once('=').ok_or_else(|| {
+                        AocError::parse_error(
+                            input,
+                            word,
+                            "expected `label=focal length` or `label-`",
+                        )
+                    })?;
+                    let focal_length = focal_length
+                        .parse::<u8>()
+                        .ok()
+                        .filter(|focal_length| (1..=9).contains(focal_length))
+                        .ok_or_else(|| {
+                            AocError::parse_error(
+                                input,
+                                focal_length,
+                                "expected a focal length from 1 to 9",
+                            )
+                        })?;
+                    Operation::Insert {
+                        label,
+                        focal_length,
+                    }
+                }
+            };
+            if operation.label().is_empty() {
+                return Err(AocError::parse_error(input, word, "expected a label"));
+            }
+            Ok(operation)
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Lens<'a> {
+    pub label: &'a str,
+    pub focal_length: u8,
+}
+
+/// The 256 boxes, every one holding its lenses from front to back.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LensBoxes<'a> {
+    boxes: Vec<Vec<Lens<'a>>>,
+}
+
+impl Default for LensBoxes<'_> {
+    fn default() -> Self {
+        LensBoxes {
+            boxes: vec![Vec::new(); 256],
+        }
+    }
+}
+
+impl<'a> LensBoxes<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replaces the lens with the same label in place or puts the new one at
+    /// the back, removing closes the gap behind the lens.
+    pub fn apply(&mut self, operation: &Operation<'a>) {
+        let lenses = &mut self.boxes[operation.target()];
+        let position = lenses
+            .iter()
+            .position(|lens| lens.label == operation.label());
+        match (*operation, position) {
+            (
+                Operation::Insert {
+                    label,
+                    focal_length,
+                },
+                None,
+            ) => lenses.push(Lens {
+                label,
+                focal_length,
+            }),
+            (Operation::Insert { focal_length, .. }, Some(position)) => {
+                lenses[position].focal_length = focal_length;
+            }
+            (Operation::Remove { .. }, Some(position)) => {
+                lenses.remove(position);
+            }
+            (Operation::Remove { .. }, None) => {}
+        }
+    }
+
+    pub fn lenses(&self, index: usize) -> &[Lens<'a>] {
+        &self.boxes[index]
+    }
+
+    /// The sum over every lens of one plus its box, times its slot counted
+    /// from one, times its focal length.
+    pub fn focusing_power(&self) -> u64 {
+        self.boxes
+            .iter()
+            .enumerate()
+            .flat_map(|(index, lenses)| {
+                lenses.iter().enumerate().map(move |(slot, lens)| {
+                    (index as u64 + 1) * (slot as u64 + 1) * u64::from(lens.focal_length)
+                })
+            })
+            .sum()
+    }
+
+    /// A copy of the boxes that hold any lens.
+    pub fn snapshot(&self) -> Snapshot<'a> {
+        Snapshot(
+            self.boxes
+                .iter()
+                .enumerate()
+                .filter(|(_, lenses)| !lenses.is_empty())
+                .map(|(index, lenses)| (index, lenses.clone()))
+                .collect(),
+        )
+    }
+}
+
+/// The non-empty boxes at one point of the sequence, by index.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Snapshot<'a>(pub Vec<(usize, Vec<Lens<'a>>)>);
+
+/// One line per box, like `Box 3: [pc 4] [ot 9]` in the puzzle statement.
+impl fmt::Display for Snapshot<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, lenses) in &self.0 {
+            write!(f, "Box {index}:")?;
+            for lens in lenses {
+                write!(f, " [{} {}]", lens.label, lens.focal_length)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// Applies the operations one at a time, with the boxes after each of them.
+pub fn replay<'a, 'o>(
+    operations: &'o [Operation<'a>],
+) -> impl Iterator<Item = (&'o Operation<'a>, Snapshot<'a>)> {
+    let mut boxes = LensBoxes::new();
+    operations.iter().map(move |operation| {
+        boxes.apply(operation);
+        (operation, boxes.snapshot())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7";
+
+    #[test]
+    fn test_hash() {
+        assert_eq!(52, hash("HASH"));
+        assert_eq!(0, hash("rn"));
+        assert_eq!(3, hash("pc"));
+    }
+
+    #[test]
+    fn test_parse() -> miette::Result<()> {
+        let operations = parse("rn=1,cm-\n")?;
+        assert_eq!(
+            vec![
+                Operation::Insert {
+                    label: "rn",
+                    focal_length: 1
+                },
+                Operation::Remove { label: "cm" }
+            ],
+            operations
+        );
+        assert_eq!(
+            "rn=1,cm-",
+            operations
+                .iter()
+                .map(|op| op.to_string())
+                .collect::<Vec<_>>()
+                .join(",")
+        );
+        assert!(parse("rn=x").is_err());
+        assert!(parse("rn").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_steps() {
+        for (input, column) in [
+            ("rn=1,=5", 6),
+            ("-,rn=1", 1),
+            ("rn=0", 4),
+            ("rn=10", 4),
+            ("rn=1,cm=", 9),
+        ] {
+            match parse(input) {
+                Err(AocError::ParseError {
+                    line, column: at, ..
+                }) => assert_eq!((1, column), (line, at), "{input}"),
+                result => panic!("expected a parse error for {input}, got {result:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_apply() -> miette::Result<()> {
+        let mut boxes = LensBoxes::new();
+        for operation in parse(INPUT)? {
+            boxes.apply(&operation);
+        }
+        let labels = |index| {
+            boxes
+                .lenses(index)
+                .iter()
+                .map(|lens| (lens.label, lens.focal_length))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(vec![("rn", 1), ("cm", 2)], labels(0));
+        assert_eq!(vec![("ot", 7), ("ab", 5), ("pc", 6)], labels(3));
+        assert_eq!(145, boxes.focusing_power());
+        assert_eq!(
+            vec![0, 3],
+            boxes
+                .snapshot()
+                .0
+                .iter()
+                .map(|(index, _)| *index)
+                .collect::<Vec<_>>()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_replay() -> miette::Result<()> {
+        let operations = parse(INPUT)?;
+        let trace = replay(&operations)
+            .map(|(operation, snapshot)| format!("After \"{operation}\":\n{snapshot}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let expected = r#"After "rn=1":
+Box 0: [rn 1]
+
+After "cm-":
+Box 0: [rn 1]
+
+After "qp=3":
+Box 0: [rn 1]
+Box 1: [qp 3]
+
+After "cm=2":
+Box 0: [rn 1] [cm 2]
+Box 1: [qp 3]
+
+After "qp-":
+Box 0: [rn 1] [cm 2]
+
+After "pc=4":
+Box 0: [rn 1] [cm 2]
+Box 3: [pc 4]
+
+After "ot=9":
+Box 0: [rn 1] [cm 2]
+Box 3: [pc 4] [ot 9]
+
+After "ab=5":
+Box 0: [rn 1] [cm 2]
+Box 3: [pc 4] [ot 9] [ab 5]
+
+After "pc-":
+Box 0: [rn 1] [cm 2]
+Box 3: [ot 9] [ab 5]
+
+After "pc=6":
+Box 0: [rn 1] [cm 2]
+Box 3: [ot 9] [ab 5] [pc 6]
+
+After "ot=7":
+Box 0: [rn 1] [cm 2]
+Box 3: [ot 7] [ab 5] [pc 6]
+"#;
+        assert_eq!(expected, trace);
+        Ok(())
+    }
+}
@@ -1,4 +1,8 @@
 pub mod custom_error;
 
+pub mod lens_boxes;
 pub mod part1;
 pub mod part2;
+
+pub const DAY: u8 = 15;
+pub const TITLE: &str = "Lens Library";
@@ -1,15 +1,43 @@
+use aoc_core::Solution;
+
 use crate::custom_error::AocError;
+use crate::lens_boxes::hash;
 
 #[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let res = input.split(',').fold(0_u32, |sum, word| {
-        sum + word
-            .bytes()
-            .fold(0_u8, |acc, byte| acc.wrapping_add(byte).wrapping_mul(17)) as u32
-    });
+pub fn parse(input: &str) -> miette::Result<Vec<&str>, AocError> {
+    Ok(input.trim_end().split(',').collect())
+}
+
+#[tracing::instrument(skip(steps))]
+pub fn solve(steps: &[&str]) -> miette::Result<String, AocError> {
+    let res = steps.iter().map(|word| u32::from(hash(word))).sum::<u32>();
     Ok(res.to_string())
 }
 
+#[tracing::instrument]
+pub fn process(input: &str) -> miette::Result<String, AocError> {
+    solve(&parse(input)?)
+}
+
+pub struct Part1;
+
+impl Solution for Part1 {
+    const DAY: u8 = crate::DAY;
+    const PART: u8 = 1;
+    const TITLE: &'static str = crate::TITLE;
+
+    type Model<'a> = Vec<&'a str>;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Self::Model<'_>, AocError> {
+        parse(input)
+    }
+
+    fn solve(steps: &Self::Model<'_>, _params: &Self::Params) -> Result<String, AocError> {
+        solve(steps)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -20,4 +48,12 @@ mod tests {
         assert_eq!("1320", process(input)?);
         Ok(())
     }
+
+    #[test]
+    fn test_process_with_trailing_newline() -> miette::Result<()> {
+        let input = "rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7";
+        assert_eq!("1320", process(&format!("{input}\n"))?);
+        assert_eq!("1320", process(&format!("{input}\r\n"))?);
+        Ok(())
+    }
 }
@@ -0,0 +1,28 @@
+use day_16::{
+    part1::parse,
+    render::{Picture, Render},
+};
+use miette::Context;
+
+/// Draws the tiles a beam energises, by default the one of part 1, or with
+/// `--param heatmap=true` how many of the part 2 edge entries energise each
+/// tile. `--param svg=true` prints an SVG image:
+///
+/// cargo run -p day-16 --bin render -- --param row=0 --param column=3 --param direction=down
+/// cargo run -p day-16 --bin render -- --param heatmap=true --param svg=true > heatmap.svg
+#[tracing::instrument]
+fn main() -> miette::Result<()> {
+    tracing_subscriber::fmt::init();
+
+    let (file, render) = aoc_core::input::from_args_with_params::<Render>(day_16::DAY, 1)?;
+    let contraption = parse(&file).context("parse the contraption")?;
+    let picture = match render.heatmap {
+        true => Picture::heatmap(&contraption),
+        false => Picture::beam(&contraption, render.entry()).context("follow the beam")?,
+    };
+    match render.svg {
+        true => print!("{}", picture.svg()),
+        false => print!("{}", picture.ansi()),
+    }
+    Ok(())
+}
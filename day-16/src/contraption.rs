@@ -0,0 +1,494 @@
+use std::{fmt, str::FromStr};
+
+use aoc_core::parse::grid_of;
+use rayon::prelude::*;
+
+use crate::custom_error::AocError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Down,
+        Direction::Left,
+        Direction::Right,
+    ];
+
+    fn bit(self) -> u8 {
+        1 << self as u8
+    }
+}
+
+impl fmt::Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Direction::Up => "up",
+            Direction::Down => "down",
+            Direction::Left => "left",
+            Direction::Right => "right",
+        };
+        f.write_str(name)
+    }
+}
+
+impl FromStr for Direction {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        Direction::ALL
+            .into_iter()
+            .find(|direction| direction.to_string() == text)
+            .ok_or_else(|| "is not one of up, down, left or right".to_string())
+    }
+}
+
+/// The directions beams travel in through one tile, a bit per [`Direction`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Beams(u8);
+
+impl Beams {
+    pub fn contains(self, direction: Direction) -> bool {
+        self.0 & direction.bit() != 0
+    }
+
+    /// Adds the direction, `false` when it was there already
+    pub fn insert(&mut self, direction: Direction) -> bool {
+        let new = !self.contains(direction);
+        self.0 |= direction.bit();
+        new
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn len(self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn iter(self) -> impl Iterator<Item = Direction> {
+        Direction::ALL
+            .into_iter()
+            .filter(move |direction| self.contains(*direction))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tile {
+    /// `.`
+    Empty,
+    /// `|`
+    VerticalSplitter,
+    /// `-`
+    HorizontalSplitter,
+    /// `\`
+    BackMirror,
+    /// `/`
+    ForwardMirror,
+}
+
+impl Tile {
+    fn new(byte: u8) -> Self {
+        match byte {
+            b'|' => Tile::VerticalSplitter,
+            b'-' => Tile::HorizontalSplitter,
+            b'\\' => Tile::BackMirror,
+            b'/' => Tile::ForwardMirror,
+            _ => Tile::Empty,
+        }
+    }
+
+    pub fn symbol(self) -> char {
+        match self {
+            Tile::Empty => '.',
+            Tile::VerticalSplitter => '|',
+            Tile::HorizontalSplitter => '-',
+            Tile::BackMirror => '\\',
+            Tile::ForwardMirror => '/',
+        }
+    }
+
+    /// The directions a beam heading `direction` leaves the tile in, the
+    /// second one only when a splitter splits it.
+    pub fn outputs(self, direction: Direction) -> (Direction, Option<Direction>) {
+        use Direction::*;
+        match (self, direction) {
+            (Tile::VerticalSplitter, Left | Right) => (Up, Some(Down)),
+            (Tile::HorizontalSplitter, Up | Down) => (Left, Some(Right)),
+            (Tile::BackMirror, Up) | (Tile::ForwardMirror, Down) => (Left, None),
+            (Tile::BackMirror, Down) | (Tile::ForwardMirror, Up) => (Right, None),
+            (Tile::BackMirror, Left) | (Tile::ForwardMirror, Right) => (Up, None),
+            (Tile::BackMirror, Right) | (Tile::ForwardMirror, Left) => (Down, None),
+            (_, direction) => (direction, None),
+        }
+    }
+}
+
+/// A beam entering the tile at `position` heading `direction`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Beam {
+    pub position: (usize, usize),
+    pub direction: Direction,
+}
+
+impl fmt::Display for Beam {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (y, x) = self.position;
+        write!(f, "row {y}, column {x} heading {}", self.direction)
+    }
+}
+
+/// The tiles beams pass through, one bit per tile in row-major order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Energized {
+    width: usize,
+    tiles: Vec<u64>,
+}
+
+impl Energized {
+    pub fn contains(&self, (y, x): (usize, usize)) -> bool {
+        let index = y * self.width + x;
+        self.tiles[index / 64] >> (index % 64) & 1 == 1
+    }
+
+    pub fn count(&self) -> usize {
+        self.tiles
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    /// The positions of the energised tiles, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.tiles.iter().enumerate().flat_map(move |(i, word)| {
+            let mut word = *word;
+            std::iter::from_fn(move || {
+                let bit = (word != 0).then(|| word.trailing_zeros() as usize)?;
+                word &= word - 1;
+                let index = i * 64 + bit;
+                Some((index / self.width, index % self.width))
+            })
+        })
+    }
+}
+
+/// Everything a splitter lights up once a beam hits its flat side: the tiles
+/// up to the next splitters hit on their flat side, and those splitters.
+#[derive(Debug, Clone)]
+struct Segment {
+    tiles: Vec<u64>,
+    next: Vec<usize>,
+}
+
+/// The grid of mirrors and splitters.
+///
+/// A splitter sends the same two beams out whichever flat side a beam hits,
+/// so the path of a beam only depends on the first splitter it hits. The
+/// [`Segment`] of every splitter is walked once when parsing, and a beam
+/// from the edge energises its own path plus the segments of every splitter
+/// reachable from there.
+#[derive(Debug, Clone)]
+pub struct Contraption {
+    width: usize,
+    height: usize,
+    tiles: Vec<Tile>,
+    /// The index into `segments` for every splitter tile
+    splitters: Vec<Option<usize>>,
+    segments: Vec<Segment>,
+}
+
+impl Contraption {
+    pub fn parse(input: &str) -> Result<Self, AocError> {
+        let lines = grid_of(input, r#"./\|-"#)?;
+        let (width, height) = (lines[0].len(), lines.len());
+        if width == 0 {
+            return Err(AocError::invalid_input("the grid is empty"));
+        }
+        let tiles = lines
+            .iter()
+            .flat_map(|line| line.bytes().map(Tile::new))
+            .collect::<Vec<Tile>>();
+        let mut count = 0;
+        let splitters = tiles
+            .iter()
+            .map(|tile| {
+                matches!(tile, Tile::VerticalSplitter | Tile::HorizontalSplitter).then(|| {
+                    count += 1;
+                    count - 1
+                })
+            })
+            .collect();
+        let mut contraption = Contraption {
+            width,
+            height,
+            tiles,
+            splitters,
+            segments: Vec::new(),
+        };
+        contraption.segments = (0..contraption.tiles.len())
+            .filter(|index| contraption.splitters[*index].is_some())
+            .map(|index| contraption.segment(index))
+            .collect();
+        Ok(contraption)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn tile(&self, (y, x): (usize, usize)) -> Tile {
+        self.tiles[y * self.width + x]
+    }
+
+    /// The position one tile over, `None` past the edge.
+    fn step(&self, (y, x): (usize, usize), direction: Direction) -> Option<(usize, usize)> {
+        match direction {
+            Direction::Up => Some((y.checked_sub(1)?, x)),
+            Direction::Down => Some((y + 1, x)).filter(|(y, _)| *y < self.height),
+            Direction::Left => Some((y, x.checked_sub(1)?)),
+            Direction::Right => Some((y, x + 1)).filter(|(_, x)| *x < self.width),
+        }
+    }
+
+    fn beam(&self, position: (usize, usize), direction: Direction) -> Option<Beam> {
+        self.step(position, direction).map(|position| Beam {
+            position,
+            direction,
+        })
+    }
+
+    /// Follows `beam` and marks its tiles until it leaves the grid, hits a
+    /// splitter on its flat side, which goes into `next`, or comes back to
+    /// the splitter at `origin`.
+    ///
+    /// Without splitting, every tile and direction has exactly one tile and
+    /// direction before it, so the only way for the beam to loop is through
+    /// the splitter it came from, and a beam from the edge never loops.
+    fn walk(&self, beam: Beam, origin: Option<usize>, tiles: &mut [u64], next: &mut Vec<usize>) {
+        let mut beam = Some(beam);
+        while let Some(Beam {
+            position,
+            direction,
+        }) = beam
+        {
+            let index = position.0 * self.width + position.1;
+            if Some(index) == origin {
+                break;
+            }
+            tiles[index / 64] |= 1 << (index % 64);
+            match self.tiles[index].outputs(direction) {
+                (_, Some(_)) => {
+                    next.extend(self.splitters[index]);
+                    break;
+                }
+                (out, None) => beam = self.beam(position, out),
+            }
+        }
+    }
+
+    fn segment(&self, index: usize) -> Segment {
+        let mut tiles = vec![0; self.tiles.len().div_ceil(64)];
+        let mut next = Vec::new();
+        tiles[index / 64] |= 1 << (index % 64);
+        let position = (index / self.width, index % self.width);
+        let outputs = match self.tiles[index] {
+            Tile::VerticalSplitter => [Direction::Up, Direction::Down],
+            _ => [Direction::Left, Direction::Right],
+        };
+        for direction in outputs {
+            if let Some(beam) = self.beam(position, direction) {
+                self.walk(beam, Some(index), &mut tiles, &mut next);
+            }
+        }
+        next.sort_unstable();
+        next.dedup();
+        Segment { tiles, next }
+    }
+
+    /// The tiles a beam coming in as `entry` energises.
+    pub fn energized(&self, entry: Beam) -> Energized {
+        let mut tiles = vec![0; self.tiles.len().div_ceil(64)];
+        let mut stack = Vec::new();
+        self.walk(entry, None, &mut tiles, &mut stack);
+        let mut seen = vec![false; self.segments.len()];
+        while let Some(splitter) = stack.pop() {
+            if std::mem::replace(&mut seen[splitter], true) {
+                continue;
+            }
+            let segment = &self.segments[splitter];
+            for (word, segment) in tiles.iter_mut().zip(&segment.tiles) {
+                *word |= segment;
+            }
+            stack.extend(segment.next.iter().filter(|next| !seen[**next]));
+        }
+        Energized {
+            width: self.width,
+            tiles,
+        }
+    }
+
+    /// Follows every beam tile by tile and keeps the directions they pass
+    /// each tile in, slower than [`energized`](Self::energized) but it shows
+    /// where the light goes.
+    pub fn beams(&self, entry: Beam) -> Vec<Beams> {
+        let mut beams = vec![Beams::default(); self.tiles.len()];
+        let mut stack = vec![entry];
+        while let Some(Beam {
+            position,
+            direction,
+        }) = stack.pop()
+        {
+            let index = position.0 * self.width + position.1;
+            if !beams[index].insert(direction) {
+                continue;
+            }
+            let (first, second) = self.tiles[index].outputs(direction);
+            stack.extend(self.beam(position, first));
+            stack.extend(second.and_then(|second| self.beam(position, second)));
+        }
+        beams
+    }
+
+    /// Every beam coming in from the edge: along each row from the left and
+    /// the right, then along each column from the top and the bottom.
+    pub fn edge_entries(&self) -> Vec<Beam> {
+        let (last_y, last_x) = (self.height - 1, self.width - 1);
+        let rows = (0..self.height)
+            .flat_map(|y| [((y, 0), Direction::Right), ((y, last_x), Direction::Left)]);
+        let columns =
+            (0..self.width).flat_map(|x| [((0, x), Direction::Down), ((last_y, x), Direction::Up)]);
+        rows.chain(columns)
+            .map(|(position, direction)| Beam {
+                position,
+                direction,
+            })
+            .collect()
+    }
+
+    /// The edge entry that energises the most tiles, the first of
+    /// [`edge_entries`](Self::edge_entries) on a tie, and how many it does.
+    pub fn best_entry(&self) -> (Beam, usize) {
+        let entries = self.edge_entries();
+        let (energized, _, entry) = entries
+            .par_iter()
+            .enumerate()
+            .map(|(i, entry)| (self.energized(*entry).count(), std::cmp::Reverse(i), *entry))
+            .max_by_key(|(energized, i, _)| (*energized, *i))
+            .expect("a grid has edge entries");
+        (entry, energized)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = r#".|...\....
+|.-.\.....
+.....|-...
+........|.
+..........
+.........\
+..../.\\..
+.-.-/..|..
+.|....-|.\
+..//.|...."#;
+
+    const TOP_LEFT: Beam = Beam {
+        position: (0, 0),
+        direction: Direction::Right,
+    };
+
+    #[test]
+    fn test_energized() -> miette::Result<()> {
+        let contraption = Contraption::parse(INPUT)?;
+        let energized = contraption.energized(TOP_LEFT);
+        assert_eq!(46, energized.count());
+        assert!(energized.contains((0, 1)) && energized.contains((9, 7)));
+        assert!(!energized.contains((0, 9)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_best_entry() -> miette::Result<()> {
+        let contraption = Contraption::parse(INPUT)?;
+        let entry = Beam {
+            position: (0, 3),
+            direction: Direction::Down,
+        };
+        assert_eq!((entry, 51), contraption.best_entry());
+        assert_eq!("row 0, column 3 heading down", entry.to_string());
+        Ok(())
+    }
+
+    #[test]
+    fn test_segments_agree_with_beams() -> miette::Result<()> {
+        // The example, plus loops through mirrors that come back into the
+        // splitter they started from on its pointy side
+        for input in [
+            INPUT,
+            "/.\\..\n..|..\n\\./..",
+            "./-\\\n.|.|\n.\\-/\n....",
+            "-",
+        ] {
+            let contraption = Contraption::parse(input)?;
+            for entry in contraption.edge_entries() {
+                let beams = contraption.beams(entry);
+                let energized = contraption.energized(entry);
+                assert_eq!(
+                    beams.iter().filter(|beams| !beams.is_empty()).count(),
+                    energized.count(),
+                    "{input:?} from {entry}"
+                );
+                for (index, beams) in beams.iter().enumerate() {
+                    let position = (index / contraption.width(), index % contraption.width());
+                    assert_eq!(!beams.is_empty(), energized.contains(position));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_positions() -> miette::Result<()> {
+        let contraption = Contraption::parse(INPUT)?;
+        let energized = contraption.energized(TOP_LEFT);
+        let positions = energized.positions().collect::<Vec<_>>();
+        assert_eq!(46, positions.len());
+        assert_eq!(&[(0, 0), (0, 1), (0, 2)], &positions[..3]);
+        assert!(positions
+            .iter()
+            .all(|position| energized.contains(*position)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_direction_from_str() {
+        assert_eq!(Ok(Direction::Left), "left".parse());
+        assert!("north".parse::<Direction>().is_err());
+    }
+
+    #[test]
+    fn test_beams() {
+        let mut beams = Beams::default();
+        assert!(beams.insert(Direction::Left));
+        assert!(!beams.insert(Direction::Left));
+        assert!(beams.insert(Direction::Up));
+        assert_eq!(2, beams.len());
+        assert_eq!(
+            vec![Direction::Up, Direction::Left],
+            beams.iter().collect::<Vec<_>>()
+        );
+    }
+}
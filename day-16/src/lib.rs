@@ -1,4 +1,9 @@
 pub mod custom_error;
 
+pub mod contraption;
 pub mod part1;
 pub mod part2;
+pub mod render;
+
+pub const DAY: u8 = 16;
+pub const TITLE: &str = "The Floor Will Be Lava";
@@ -1,298 +1,44 @@
-use std::collections::VecDeque;
-
-use nom::{
-    bytes::complete::is_a, character::complete::line_ending, multi::separated_list1, IResult,
-};
+use aoc_core::Solution;
 
+use crate::contraption::{Beam, Contraption, Direction};
 use crate::custom_error::AocError;
 
-#[derive(Debug)]
-enum MoveDirection {
-    SplitVertical,
-    SplitHorizontal,
-    Up,
-    Down,
-    Left,
-    Right,
+#[tracing::instrument]
+pub fn parse(input: &str) -> miette::Result<Contraption, AocError> {
+    Contraption::parse(input)
 }
 
-#[derive(Debug)]
-enum Character {
-    VerticalBar,
-    HorizontalBar,
-    BackSlash,
-    ForwardSlash,
-    Dot,
+#[tracing::instrument(skip(contraption))]
+pub fn solve(contraption: &Contraption) -> miette::Result<String, AocError> {
+    let top_left = Beam {
+        position: (0, 0),
+        direction: Direction::Right,
+    };
+    Ok(contraption.energized(top_left).count().to_string())
 }
 
-#[derive(Debug)]
-struct Entry {
-    passed_from_down: bool,
-    passed_from_up: bool,
-    passed_from_left: bool,
-    passed_from_right: bool,
-    splittable: Option<bool>,
-    charachter: Character,
+#[tracing::instrument]
+pub fn process(input: &str) -> miette::Result<String, AocError> {
+    solve(&parse(input)?)
 }
 
-impl Entry {
-    fn new(ch: char) -> Self {
-        match ch {
-            '|' => Self {
-                passed_from_down: false,
-                passed_from_up: false,
-                passed_from_left: false,
-                passed_from_right: false,
-                splittable: Some(false),
-                charachter: Character::VerticalBar,
-            },
-            '-' => Self {
-                passed_from_down: false,
-                passed_from_up: false,
-                passed_from_left: false,
-                passed_from_right: false,
-                splittable: Some(false),
-                charachter: Character::HorizontalBar,
-            },
-            '\\' => Self {
-                passed_from_down: false,
-                passed_from_up: false,
-                passed_from_left: false,
-                passed_from_right: false,
-                splittable: None,
-                charachter: Character::BackSlash,
-            },
-            '/' => Self {
-                passed_from_down: false,
-                passed_from_up: false,
-                passed_from_left: false,
-                passed_from_right: false,
-                splittable: None,
-                charachter: Character::ForwardSlash,
-            },
-            _ => Self {
-                passed_from_down: false,
-                passed_from_up: false,
-                passed_from_left: false,
-                passed_from_right: false,
-                splittable: None,
-                charachter: Character::Dot,
-            },
-        }
-    }
-}
+pub struct Part1;
 
-fn parse_input(input: &str) -> IResult<&str, Vec<Vec<Entry>>> {
-    let (input, lines) = separated_list1(line_ending, is_a(r#"./\|-"#))(input)?;
-    let mapped_lines = lines
-        .into_iter()
-        .map(|k| k.chars().map(|ch| Entry::new(ch)).collect())
-        .collect::<Vec<Vec<Entry>>>();
-    Ok((input, mapped_lines))
-}
+impl Solution for Part1 {
+    const DAY: u8 = crate::DAY;
+    const PART: u8 = 1;
+    const TITLE: &'static str = crate::TITLE;
 
-#[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let (_, mut mapped_input) = parse_input(input).expect("Shoud be valid");
-    let mut queue = VecDeque::new();
-    match mapped_input[0][0].charachter {
-        Character::Dot | Character::HorizontalBar => {
-            queue.push_back(((0_usize, 0_usize), MoveDirection::Right))
-        }
-        _ => queue.push_back(((0_usize, 0_usize), MoveDirection::Down)),
+    type Model<'a> = Contraption;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Self::Model<'_>, AocError> {
+        parse(input)
     }
-    mapped_input[0][0].passed_from_left = true;
-    while let Some(((y, x), move_direction)) = queue.pop_front() {
-        match move_direction {
-            MoveDirection::Up
-                if y > 0
-                    && (!mapped_input[y - 1][x].passed_from_down
-                        || mapped_input[y - 1][x].splittable.is_some_and(|v| !v)) =>
-            {
-                match mapped_input[y - 1][x].charachter {
-                    Character::Dot | Character::VerticalBar => {
-                        queue.push_back(((y - 1, x), MoveDirection::Up));
-                    }
-                    Character::BackSlash => {
-                        queue.push_back(((y - 1, x), MoveDirection::Left));
-                    }
-                    Character::ForwardSlash => {
-                        queue.push_back(((y - 1, x), MoveDirection::Right));
-                    }
-                    Character::HorizontalBar => {
-                        queue.push_back(((y - 1, x), MoveDirection::SplitHorizontal));
-                        mapped_input[y - 1][x].splittable = Some(true);
-                    }
-                }
-                mapped_input[y - 1][x].passed_from_down = true;
-            }
-            MoveDirection::Down
-                if y + 1 < mapped_input.len()
-                    && (!mapped_input[y + 1][x].passed_from_up
-                        || mapped_input[y + 1][x].splittable.is_some_and(|v| !v)) =>
-            {
-                match mapped_input[y + 1][x].charachter {
-                    Character::Dot | Character::VerticalBar => {
-                        queue.push_back(((y + 1, x), MoveDirection::Down));
-                    }
-                    Character::BackSlash => {
-                        queue.push_back(((y + 1, x), MoveDirection::Right));
-                    }
-                    Character::ForwardSlash => {
-                        queue.push_back(((y + 1, x), MoveDirection::Left));
-                    }
-                    Character::HorizontalBar => {
-                        queue.push_back(((y + 1, x), MoveDirection::SplitHorizontal));
-                        mapped_input[y + 1][x].splittable = Some(true);
-                    }
-                }
-                mapped_input[y + 1][x].passed_from_up = true;
-            }
-            MoveDirection::Left
-                if x > 0
-                    && (!mapped_input[y][x - 1].passed_from_right
-                        || mapped_input[y][x - 1].splittable.is_some_and(|v| !v)) =>
-            {
-                match mapped_input[y][x - 1].charachter {
-                    Character::Dot | Character::HorizontalBar => {
-                        queue.push_back(((y, x - 1), MoveDirection::Left));
-                    }
-                    Character::BackSlash => {
-                        queue.push_back(((y, x - 1), MoveDirection::Up));
-                    }
-                    Character::ForwardSlash => {
-                        queue.push_back(((y, x - 1), MoveDirection::Down));
-                    }
-                    Character::VerticalBar => {
-                        queue.push_back(((y, x - 1), MoveDirection::SplitVertical));
-                        mapped_input[y][x - 1].splittable = Some(true);
-                    }
-                }
-                mapped_input[y][x - 1].passed_from_right = true;
-            }
-            MoveDirection::Right
-                if x + 1 < mapped_input[0].len()
-                    && (!mapped_input[y][x + 1].passed_from_left
-                        || mapped_input[y][x + 1].splittable.is_some_and(|v| !v)) =>
-            {
-                match mapped_input[y][x + 1].charachter {
-                    Character::Dot | Character::HorizontalBar => {
-                        queue.push_back(((y, x + 1), MoveDirection::Right));
-                    }
-                    Character::BackSlash => {
-                        queue.push_back(((y, x + 1), MoveDirection::Down));
-                    }
-                    Character::ForwardSlash => {
-                        queue.push_back(((y, x + 1), MoveDirection::Up));
-                    }
-                    Character::VerticalBar => {
-                        queue.push_back(((y, x + 1), MoveDirection::SplitVertical));
-                        mapped_input[y][x + 1].splittable = Some(true);
-                    }
-                }
-                mapped_input[y][x + 1].passed_from_left = true;
-            }
-            MoveDirection::SplitVertical => {
-                if y > 0
-                    && (!mapped_input[y - 1][x].passed_from_down
-                        || mapped_input[y - 1][x].splittable.is_some_and(|v| !v))
-                {
-                    match mapped_input[y - 1][x].charachter {
-                        Character::Dot | Character::VerticalBar => {
-                            queue.push_back(((y - 1, x), MoveDirection::Up));
-                        }
-                        Character::BackSlash => {
-                            queue.push_back(((y - 1, x), MoveDirection::Left));
-                        }
-                        Character::ForwardSlash => {
-                            queue.push_back(((y - 1, x), MoveDirection::Right));
-                        }
-                        Character::HorizontalBar => {
-                            queue.push_back(((y - 1, x), MoveDirection::SplitHorizontal));
-                            mapped_input[y - 1][x].splittable = Some(true);
-                        }
-                    }
-                    mapped_input[y - 1][x].passed_from_down = true;
-                }
-                if y + 1 < mapped_input.len()
-                    && (!mapped_input[y + 1][x].passed_from_up
-                        || mapped_input[y + 1][x].splittable.is_some_and(|v| !v))
-                {
-                    match mapped_input[y + 1][x].charachter {
-                        Character::Dot | Character::VerticalBar => {
-                            queue.push_back(((y + 1, x), MoveDirection::Down));
-                        }
-                        Character::BackSlash => {
-                            queue.push_back(((y + 1, x), MoveDirection::Right));
-                        }
-                        Character::ForwardSlash => {
-                            queue.push_back(((y + 1, x), MoveDirection::Left));
-                        }
-                        Character::HorizontalBar => {
-                            queue.push_back(((y + 1, x), MoveDirection::SplitHorizontal));
-                            mapped_input[y + 1][x].splittable = Some(true);
-                        }
-                    }
-                    mapped_input[y + 1][x].passed_from_up = true;
-                }
-            }
-            MoveDirection::SplitHorizontal => {
-                if x > 0
-                    && (!mapped_input[y][x - 1].passed_from_right
-                        || mapped_input[y][x - 1].splittable.is_some_and(|v| !v))
-                {
-                    match mapped_input[y][x - 1].charachter {
-                        Character::Dot | Character::HorizontalBar => {
-                            queue.push_back(((y, x - 1), MoveDirection::Left));
-                        }
-                        Character::BackSlash => {
-                            queue.push_back(((y, x - 1), MoveDirection::Up));
-                        }
-                        Character::ForwardSlash => {
-                            queue.push_back(((y, x - 1), MoveDirection::Down));
-                        }
-                        Character::VerticalBar => {
-                            queue.push_back(((y, x - 1), MoveDirection::SplitVertical));
-                            mapped_input[y][x - 1].splittable = Some(true);
-                        }
-                    }
-                    mapped_input[y][x - 1].passed_from_right = true;
-                }
-                if x + 1 < mapped_input[0].len()
-                    && (!mapped_input[y][x + 1].passed_from_left
-                        || mapped_input[y][x + 1].splittable.is_some_and(|v| !v))
-                {
-                    match mapped_input[y][x + 1].charachter {
-                        Character::Dot | Character::HorizontalBar => {
-                            queue.push_back(((y, x + 1), MoveDirection::Right));
-                        }
-                        Character::BackSlash => {
-                            queue.push_back(((y, x + 1), MoveDirection::Down));
-                        }
-                        Character::ForwardSlash => {
-                            queue.push_back(((y, x + 1), MoveDirection::Up));
-                        }
-                        Character::VerticalBar => {
-                            queue.push_back(((y, x + 1), MoveDirection::SplitVertical));
-                            mapped_input[y][x + 1].splittable = Some(true);
-                        }
-                    }
-                    mapped_input[y][x + 1].passed_from_left = true;
-                }
-            }
-            _ => {}
-        }
+
+    fn solve(contraption: &Self::Model<'_>, _params: &Self::Params) -> Result<String, AocError> {
+        solve(contraption)
     }
-    let res = mapped_input.into_iter().fold(0_u32, |acc, row| {
-        acc + row.into_iter().fold(0_u32, |acc_row, k| {
-            if k.passed_from_down || k.passed_from_left || k.passed_from_right || k.passed_from_up {
-                acc_row + 1
-            } else {
-                acc_row
-            }
-        })
-    });
-    Ok(res.to_string())
 }
 
 #[cfg(test)]
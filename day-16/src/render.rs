@@ -0,0 +1,327 @@
+use std::fmt::Write;
+
+use rayon::prelude::*;
+
+use crate::contraption::{Beam, Contraption, Direction, Tile};
+use crate::custom_error::AocError;
+
+aoc_core::params! {
+    /// How the `render` binary draws the contraption
+    pub struct Render {
+        /// Print an SVG image instead of coloured text
+        svg: bool = false,
+        /// Shade every tile by how many edge entries energise it instead of following one beam
+        heatmap: bool = false,
+        /// The row the beam comes in at
+        row: usize = 0,
+        /// The column the beam comes in at
+        column: usize = 0,
+        /// The direction the beam comes in heading, `up`, `down`, `left` or `right`
+        direction: Direction = Direction::Right,
+    }
+}
+
+impl Render {
+    pub fn entry(&self) -> Beam {
+        Beam {
+            position: (self.row, self.column),
+            direction: self.direction,
+        }
+    }
+}
+
+/// The contraption with a heat for every tile, ready to be drawn: `1` for
+/// the tiles one beam energises, or the number of edge entries that do for
+/// a heatmap.
+#[derive(Debug, Clone)]
+pub struct Picture {
+    width: usize,
+    tiles: Vec<Tile>,
+    heat: Vec<usize>,
+    /// The hottest tile, the top of the colour scale
+    max: usize,
+    /// The beam for a single entry, `None` for a heatmap
+    entry: Option<Beam>,
+}
+
+/// Background colours of the 256 colour palette from cold to hot.
+const RAMP: [u8; 8] = [236, 52, 88, 124, 160, 202, 208, 220];
+const CELL: usize = 12;
+const RESET: &str = "\x1b[0m";
+
+impl Picture {
+    /// The tiles energised by a beam coming in as `entry`.
+    pub fn beam(contraption: &Contraption, entry: Beam) -> Result<Self, AocError> {
+        let (y, x) = entry.position;
+        for (name, value, len) in [
+            ("row", y, contraption.height()),
+            ("column", x, contraption.width()),
+        ] {
+            if value >= len {
+                return Err(AocError::InvalidParam {
+                    name: name.to_string(),
+                    message: format!("{value} is outside of the {len} tiles of the grid"),
+                });
+            }
+        }
+        let mut heat = vec![0; contraption.width() * contraption.height()];
+        for (y, x) in contraption.energized(entry).positions() {
+            heat[y * contraption.width() + x] = 1;
+        }
+        Ok(Picture::new(contraption, heat, Some(entry)))
+    }
+
+    /// How many of the [`edge_entries`](Contraption::edge_entries) energise
+    /// every tile.
+    pub fn heatmap(contraption: &Contraption) -> Self {
+        let tiles = contraption.width() * contraption.height();
+        let heat = contraption
+            .edge_entries()
+            .par_iter()
+            .fold(
+                || vec![0; tiles],
+                |mut heat, entry| {
+                    for (y, x) in contraption.energized(*entry).positions() {
+                        heat[y * contraption.width() + x] += 1;
+                    }
+                    heat
+                },
+            )
+            .reduce(
+                || vec![0; tiles],
+                |mut heat, other| {
+                    for (heat, other) in heat.iter_mut().zip(other) {
+                        *heat += other;
+                    }
+                    heat
+                },
+            );
+        Picture::new(contraption, heat, None)
+    }
+
+    fn new(contraption: &Contraption, heat: Vec<usize>, entry: Option<Beam>) -> Self {
+        let tiles = (0..contraption.height())
+            .flat_map(|y| (0..contraption.width()).map(move |x| (y, x)))
+            .map(|position| contraption.tile(position))
+            .collect();
+        Picture {
+            width: contraption.width(),
+            tiles,
+            max: heat.iter().copied().max().unwrap_or(0),
+            heat,
+            entry,
+        }
+    }
+
+    pub fn heat(&self, (y, x): (usize, usize)) -> usize {
+        self.heat[y * self.width + x]
+    }
+
+    pub fn max(&self) -> usize {
+        self.max
+    }
+
+    /// The step of [`RAMP`] for a heat, only a cold tile gets the first one.
+    fn level(&self, heat: usize) -> usize {
+        (heat * (RAMP.len() - 1)).div_ceil(self.max.max(1))
+    }
+
+    /// The grid with ANSI background colours by heat. Mirrors and splitters
+    /// keep their symbol in bold, empty tiles show `#` when one beam
+    /// energises them like in the puzzle statement and nothing on a heatmap.
+    pub fn ansi(&self) -> String {
+        let mut out = String::new();
+        for (y, row) in self.tiles.chunks(self.width).enumerate() {
+            for (x, tile) in row.iter().enumerate() {
+                let heat = self.heat((y, x));
+                let level = self.level(heat);
+                let glyph = match (tile, self.entry, heat) {
+                    (Tile::Empty, None, _) => ' ',
+                    (Tile::Empty, Some(_), 0) => '.',
+                    (Tile::Empty, Some(_), _) => '#',
+                    (tile, _, _) => tile.symbol(),
+                };
+                let weight = match tile {
+                    Tile::Empty => "2",
+                    _ => "1",
+                };
+                let _ = write!(out, "\x1b[{weight};97;48;5;{}m{glyph}{RESET}", RAMP[level]);
+            }
+            out.push('\n');
+        }
+        out
+    }
+
+    /// The grid as an SVG image with one `CELL` sized square per tile: hot
+    /// tiles filled, more opaque the hotter they are, mirrors and splitters
+    /// as lines and a dot where the beam comes in.
+    pub fn svg(&self) -> String {
+        let height = self.tiles.len() / self.width * CELL;
+        let width = self.width * CELL;
+        let mut out = String::new();
+        let _ = writeln!(
+            out,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}">"#
+        );
+        out.push_str(
+            "<style>.floor{fill:#1d1f24}.heat{fill:#ffb703}\
+             .device{stroke:#e9ecef;stroke-width:1.5;fill:none;stroke-linecap:round}\
+             .entry{fill:#e63946}</style>\n",
+        );
+        let _ = writeln!(
+            out,
+            r#"<rect class="floor" width="{width}" height="{height}"/>"#
+        );
+        // One rectangle per run of tiles with the same heat in a row
+        for (y, heat) in self.heat.chunks(self.width).enumerate() {
+            for run in heat
+                .iter()
+                .enumerate()
+                .collect::<Vec<_>>()
+                .chunk_by(|(_, a), (_, b)| a == b)
+            {
+                let (x, heat) = run[0];
+                if *heat == 0 {
+                    continue;
+                }
+                let _ = writeln!(
+                    out,
+                    r#"<rect class="heat" x="{}" y="{}" width="{}" height="{CELL}" fill-opacity="{:.3}"/>"#,
+                    x * CELL,
+                    y * CELL,
+                    run.len() * CELL,
+                    *heat as f64 / self.max as f64
+                );
+            }
+        }
+        let mut devices = String::new();
+        for (index, tile) in self.tiles.iter().enumerate() {
+            let (left, top) = (index % self.width * CELL, index / self.width * CELL);
+            let (half, cell) = (CELL / 2, CELL as isize);
+            let _ = match tile {
+                Tile::Empty => continue,
+                Tile::VerticalSplitter => write!(devices, "M{} {top}v{cell}", left + half),
+                Tile::HorizontalSplitter => write!(devices, "M{left} {}h{cell}", top + half),
+                Tile::BackMirror => write!(devices, "M{left} {top}l{cell} {cell}"),
+                Tile::ForwardMirror => write!(devices, "M{left} {}l{cell} {}", top + CELL, -cell),
+            };
+        }
+        if !devices.is_empty() {
+            let _ = writeln!(out, r#"<path class="device" d="{devices}"/>"#);
+        }
+        if let Some(Beam {
+            position: (y, x), ..
+        }) = self.entry
+        {
+            let _ = writeln!(
+                out,
+                r#"<circle class="entry" cx="{}" cy="{}" r="{}"/>"#,
+                x * CELL + CELL / 2,
+                y * CELL + CELL / 2,
+                CELL / 4
+            );
+        }
+        out.push_str("</svg>\n");
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = r#".|...\....
+|.-.\.....
+.....|-...
+........|.
+..........
+.........\
+..../.\\..
+.-.-/..|..
+.|....-|.\
+..//.|...."#;
+
+    #[test]
+    fn test_ansi() -> miette::Result<()> {
+        let contraption = Contraption::parse(INPUT)?;
+        let picture = Picture::beam(&contraption, Render::default().entry())?;
+        let plain = picture
+            .ansi()
+            .split('\x1b')
+            .map(|part| part.split_once('m').map_or(part, |(_, text)| text))
+            .collect::<String>();
+        // The energised tiles from the puzzle statement, under the mirrors and splitters
+        let energized = "######....
+.#...#....
+.#...#####
+.#...##...
+.#...##...
+.#...##...
+.#..####..
+########..
+.#######..
+.#...#.#..";
+        let expected = INPUT
+            .lines()
+            .zip(energized.lines())
+            .map(|(tiles, energized)| {
+                let row = tiles
+                    .chars()
+                    .zip(energized.chars())
+                    .map(|(tile, energized)| if tile == '.' { energized } else { tile })
+                    .collect::<String>();
+                row + "\n"
+            })
+            .collect::<String>();
+        assert_eq!(expected, plain);
+        Ok(())
+    }
+
+    #[test]
+    fn test_heatmap() -> miette::Result<()> {
+        let contraption = Contraption::parse(INPUT)?;
+        let picture = Picture::heatmap(&contraption);
+        let entries = contraption.edge_entries();
+        for position in [(0, 0), (4, 4), (9, 9)] {
+            let energizing = entries
+                .iter()
+                .filter(|entry| contraption.energized(**entry).contains(position))
+                .count();
+            assert_eq!(energizing, picture.heat(position));
+        }
+        assert!(picture.max() <= entries.len());
+        assert_eq!(0, picture.level(0));
+        assert_eq!(RAMP.len() - 1, picture.level(picture.max()));
+        assert_eq!(1, picture.level(1));
+        Ok(())
+    }
+
+    #[test]
+    fn test_entry_outside() -> miette::Result<()> {
+        let contraption = Contraption::parse(INPUT)?;
+        let render = Render {
+            row: 10,
+            ..Render::default()
+        };
+        assert!(matches!(
+            Picture::beam(&contraption, render.entry()),
+            Err(AocError::InvalidParam { name, .. }) if name == "row"
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_svg() -> miette::Result<()> {
+        let contraption = Contraption::parse(INPUT)?;
+        let svg = Picture::beam(&contraption, Render::default().entry())?.svg();
+        assert!(svg.starts_with("<svg") && svg.ends_with("</svg>\n"));
+        assert!(svg.contains(r#"width="120" height="120""#));
+        assert!(svg.contains(
+            r#"<rect class="heat" x="0" y="0" width="72" height="12" fill-opacity="1.000"/>"#
+        ));
+        assert!(svg.contains(r#"<circle class="entry" cx="6" cy="6""#));
+        let heatmap = Picture::heatmap(&contraption).svg();
+        assert!(heatmap.contains(r#"class="heat""#) && !heatmap.contains("circle"));
+        Ok(())
+    }
+}
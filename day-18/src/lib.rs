@@ -2,3 +2,6 @@ pub mod custom_error;
 
 pub mod part1;
 pub mod part2;
+
+pub const DAY: u8 = 18;
+pub const TITLE: &str = "Lavaduct Lagoon";
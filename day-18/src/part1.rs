@@ -1,12 +1,15 @@
+use aoc_core::{parse::finish, Solution};
+
 use crate::custom_error::AocError;
 
 use nom::bytes::complete::{tag, take_till};
-use nom::character::complete::{self, alpha1, line_ending};
+use nom::character::complete::{self, line_ending, one_of};
 use nom::multi::separated_list1;
-use nom::sequence::{pair, terminated, tuple};
+use nom::sequence::{terminated, tuple};
 use nom::{IResult, Parser};
 
-enum MoveDirection {
+#[derive(Debug)]
+pub enum MoveDirection {
     Up,
     Down,
     Right,
@@ -27,12 +30,11 @@ impl From<char> for MoveDirection {
 
 fn parse_input(input: &str) -> IResult<&str, Vec<(MoveDirection, i64, &str)>> {
     let (input, map) = separated_list1(
-        pair(tag(")"), line_ending),
+        line_ending,
         tuple((
-            terminated(alpha1, tag(" "))
-                .map(|k: &str| MoveDirection::from(k.parse::<char>().expect("Shoud be valid"))),
+            terminated(one_of("UDLR"), tag(" ")).map(MoveDirection::from),
             terminated(complete::i64, tag(" (")),
-            take_till(|c| c == ')'),
+            terminated(take_till(|c| c == ')'), tag(")")),
         )),
     )
     .parse(input)?;
@@ -41,8 +43,12 @@ fn parse_input(input: &str) -> IResult<&str, Vec<(MoveDirection, i64, &str)>> {
 }
 
 #[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let (_, steps_map) = parse_input(input).expect("Should be valid");
+pub fn parse(input: &str) -> miette::Result<Vec<(MoveDirection, i64, &str)>, AocError> {
+    finish(input, parse_input)
+}
+
+#[tracing::instrument(skip(steps_map))]
+pub fn solve(steps_map: &[(MoveDirection, i64, &str)]) -> miette::Result<String, AocError> {
     let mut res = 0;
     let mut length = 0;
     let mut y = 0;
@@ -58,6 +64,30 @@ pub fn process(input: &str) -> miette::Result<String, AocError> {
     Ok((res.abs() + length / 2 + 1).to_string())
 }
 
+#[tracing::instrument]
+pub fn process(input: &str) -> miette::Result<String, AocError> {
+    solve(&parse(input)?)
+}
+
+pub struct Part1;
+
+impl Solution for Part1 {
+    const DAY: u8 = crate::DAY;
+    const PART: u8 = 1;
+    const TITLE: &'static str = crate::TITLE;
+
+    type Model<'a> = Vec<(MoveDirection, i64, &'a str)>;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Self::Model<'_>, AocError> {
+        parse(input)
+    }
+
+    fn solve(steps_map: &Self::Model<'_>, _params: &Self::Params) -> Result<String, AocError> {
+        solve(steps_map)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
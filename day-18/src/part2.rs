@@ -1,12 +1,16 @@
+use aoc_core::{parse::finish, Solution};
+
 use crate::custom_error::AocError;
 
-use nom::bytes::complete::{tag, take_till};
-use nom::character::complete::{alphanumeric1, line_ending};
+use nom::bytes::complete::{tag, take, take_till};
+use nom::character::complete::{line_ending, one_of};
+use nom::combinator::map_res;
 use nom::multi::separated_list1;
-use nom::sequence::{pair, preceded};
+use nom::sequence::{delimited, pair};
 use nom::{IResult, Parser};
 
-enum MoveDirection {
+#[derive(Debug)]
+pub enum MoveDirection {
     Up,
     Down,
     Right,
@@ -25,29 +29,27 @@ impl From<char> for MoveDirection {
 }
 
 fn parse_input(input: &str) -> IResult<&str, Vec<(i64, MoveDirection)>> {
-    let (input, steps_map) = separated_list1(
-        pair(tag(")"), line_ending),
-        preceded(take_till(|c| c == '#'), preceded(tag("#"), alphanumeric1)),
+    separated_list1(
+        line_ending,
+        delimited(
+            pair(take_till(|c| c == '#' || c == '\n'), tag("#")),
+            pair(
+                map_res(take(5_usize), |hex| i64::from_str_radix(hex, 16)),
+                one_of("0123").map(MoveDirection::from),
+            ),
+            tag(")"),
+        ),
     )
-    .parse(input)?;
-
-    Ok((
-        input,
-        steps_map
-            .into_iter()
-            .map(|k| {
-                (
-                    i64::from_str_radix(&k[..k.len() - 1], 16).expect("Should be parsable"),
-                    MoveDirection::from(k.chars().last().expect("Exists")),
-                )
-            })
-            .collect(),
-    ))
+    .parse(input)
 }
 
 #[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let (_, steps_map) = parse_input(input).expect("Should be valid");
+pub fn parse(input: &str) -> miette::Result<Vec<(i64, MoveDirection)>, AocError> {
+    finish(input, parse_input)
+}
+
+#[tracing::instrument(skip(steps_map))]
+pub fn solve(steps_map: &[(i64, MoveDirection)]) -> miette::Result<String, AocError> {
     let mut res = 0;
     let mut length = 0;
     let mut y = 0;
@@ -63,6 +65,30 @@ pub fn process(input: &str) -> miette::Result<String, AocError> {
     Ok((res.abs() + length / 2 + 1).to_string())
 }
 
+#[tracing::instrument]
+pub fn process(input: &str) -> miette::Result<String, AocError> {
+    solve(&parse(input)?)
+}
+
+pub struct Part2;
+
+impl Solution for Part2 {
+    const DAY: u8 = crate::DAY;
+    const PART: u8 = 2;
+    const TITLE: &'static str = crate::TITLE;
+
+    type Model<'a> = Vec<(i64, MoveDirection)>;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Self::Model<'_>, AocError> {
+        parse(input)
+    }
+
+    fn solve(steps_map: &Self::Model<'_>, _params: &Self::Params) -> Result<String, AocError> {
+        solve(steps_map)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,16 +1,18 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+
+use aoc_core::{parse::finish, Solution};
 
 use crate::custom_error::AocError;
 
 use nom::branch::alt;
 use nom::bytes::complete::tag;
-use nom::character::complete::{self, alpha1, anychar, line_ending};
+use nom::character::complete::{self, alpha1, line_ending};
 use nom::multi::separated_list1;
-use nom::sequence::{delimited, pair, preceded, separated_pair};
+use nom::sequence::{delimited, pair, preceded, separated_pair, tuple};
 use nom::{IResult, Parser};
 
 #[derive(PartialEq, Eq, Hash, Debug)]
-enum Part {
+pub enum Part {
     Xtremly,
     Musical,
     Aerodynamic,
@@ -18,20 +20,20 @@ enum Part {
 }
 
 #[derive(PartialEq, Eq, Hash, Debug, Copy, Clone)]
-enum Destination<'a> {
+pub enum Destination<'a> {
     Workflow(&'a str),
     Accepted,
     Rejected,
 }
 
 #[derive(PartialEq, Eq, Hash, Debug)]
-enum Condition {
+pub enum Condition {
     Greater,
     Lower,
 }
 
 #[derive(PartialEq, Eq, Hash, Debug)]
-enum Rule<'a> {
+pub enum Rule<'a> {
     Test {
         part: Part,
         target: Destination<'a>,
@@ -42,7 +44,7 @@ enum Rule<'a> {
 }
 
 #[derive(Debug)]
-struct Rating<'a> {
+pub struct Rating<'a> {
     x: u32,
     m: u32,
     a: u32,
@@ -51,16 +53,6 @@ struct Rating<'a> {
 }
 
 impl<'a> Rating<'a> {
-    fn from_vec(input: Vec<u32>) -> Self {
-        Rating {
-            x: input[0],
-            m: input[1],
-            a: input[2],
-            s: input[3],
-            current_place: Destination::Workflow("in"),
-        }
-    }
-
     fn get_part_value(&self, part: &Part) -> &u32 {
         match part {
             Part::Xtremly => &self.x,
@@ -71,29 +63,36 @@ impl<'a> Rating<'a> {
     }
 }
 
-fn parse_rating(input: &str) -> IResult<&str, Vec<u32>> {
-    let (input, rating) = delimited(
+fn parse_rating(input: &str) -> IResult<&str, Rating<'_>> {
+    let (input, (x, m, a, s)) = delimited(
         complete::char('{'),
-        separated_list1(
-            complete::char(','),
-            preceded(pair(anychar, complete::char('=')), complete::u32),
-        ),
+        tuple((
+            preceded(tag("x="), complete::u32),
+            preceded(tag(",m="), complete::u32),
+            preceded(tag(",a="), complete::u32),
+            preceded(tag(",s="), complete::u32),
+        )),
         complete::char('}'),
     )
     .parse(input)?;
 
-    Ok((input, rating))
+    Ok((
+        input,
+        Rating {
+            x,
+            m,
+            a,
+            s,
+            current_place: Destination::Workflow("in"),
+        },
+    ))
 }
 
-fn parse_ratings(input: &str) -> IResult<&str, Vec<Rating>> {
-    let (input, ratings) = separated_list1(line_ending, parse_rating)
-        .map(|ratings| ratings.into_iter().map(Rating::from_vec).collect())
-        .parse(input)?;
-
-    Ok((input, ratings))
+fn parse_ratings(input: &str) -> IResult<&str, Vec<Rating<'_>>> {
+    separated_list1(line_ending, parse_rating).parse(input)
 }
 
-fn parse_destination(input: &str) -> IResult<&str, Rule> {
+fn parse_destination(input: &str) -> IResult<&str, Rule<'_>> {
     let (input, destination) = alt((
         tag("A").map(|_| Destination::Accepted),
         tag("R").map(|_| Destination::Rejected),
@@ -104,7 +103,7 @@ fn parse_destination(input: &str) -> IResult<&str, Rule> {
     Ok((input, Rule::Target(destination)))
 }
 
-fn parse_rule_test(input: &str) -> IResult<&str, Rule> {
+fn parse_rule_test(input: &str) -> IResult<&str, Rule<'_>> {
     let (input, part) = alt((
         complete::char('a').map(|_| Part::Aerodynamic),
         complete::char('x').map(|_| Part::Xtremly),
@@ -139,7 +138,7 @@ fn parse_rule_test(input: &str) -> IResult<&str, Rule> {
     ))
 }
 
-fn parse_workflow(input: &str) -> IResult<&str, (&str, Vec<Rule>)> {
+fn parse_workflow(input: &str) -> IResult<&str, (&str, Vec<Rule<'_>>)> {
     let (input, id) = alpha1(input)?;
     let (input, rules) = delimited(
         complete::char('{'),
@@ -162,14 +161,14 @@ fn parse_workflows(input: &str) -> IResult<&str, HashMap<&str, Vec<Rule<'_>>>> {
     Ok((input, workflows_map))
 }
 
-fn parse_input(input: &str) -> IResult<&str, (HashMap<&str, Vec<Rule>>, Vec<Rating>)> {
+fn parse_input(input: &str) -> IResult<&str, System<'_>> {
     let (input, (workflows, ratings)) = separated_pair(
         parse_workflows,
         pair(line_ending, line_ending),
         parse_ratings,
     )
     .parse(input)?;
-    Ok((input, (workflows, ratings)))
+    Ok((input, System { workflows, ratings }))
 }
 
 fn check_acceptance<'a>(rating: &'a Rating<'a>, map: &'a HashMap<&str, Vec<Rule<'a>>>) -> bool {
@@ -207,12 +206,96 @@ fn check_acceptance<'a>(rating: &'a Rating<'a>, map: &'a HashMap<&str, Vec<Rule<
     current_place == Destination::Accepted
 }
 
+#[derive(Debug)]
+pub struct System<'a> {
+    pub workflows: HashMap<&'a str, Vec<Rule<'a>>>,
+    pub ratings: Vec<Rating<'a>>,
+}
+
+/// Checks the `in` workflow exists, every workflow ends in an unconditional rule,
+/// every rule sends the parts to a known workflow and no part can go round in circles
+fn check_workflows(input: &str, workflows: &HashMap<&str, Vec<Rule<'_>>>) -> Result<(), AocError> {
+    if !workflows.contains_key("in") {
+        return Err(AocError::invalid_input("there is no `in` workflow"));
+    }
+    if let Some((name, _)) = workflows
+        .iter()
+        .find(|(_, rules)| !matches!(rules.last(), Some(Rule::Target(_))))
+    {
+        return Err(AocError::parse_error(
+            input,
+            name,
+            "the last rule of a workflow can not have a condition",
+        ));
+    }
+    let targets = workflows.values().flatten().map(|rule| match rule {
+        Rule::Test { target, .. } => target,
+        Rule::Target(target) => target,
+    });
+    for target in targets {
+        if let Destination::Workflow(name) = target {
+            if !workflows.contains_key(name) {
+                return Err(AocError::parse_error(
+                    input,
+                    name,
+                    "this workflow is never defined",
+                ));
+            }
+        }
+    }
+    let mut finished = HashSet::new();
+    for name in workflows.keys() {
+        check_cycles(input, workflows, name, &mut Vec::new(), &mut finished)?;
+    }
+    Ok(())
+}
+
+/// Depth first search from `name`, `path` holds the workflows leading to it
+fn check_cycles<'a>(
+    input: &str,
+    workflows: &HashMap<&'a str, Vec<Rule<'a>>>,
+    name: &'a str,
+    path: &mut Vec<&'a str>,
+    finished: &mut HashSet<&'a str>,
+) -> Result<(), AocError> {
+    if finished.contains(name) {
+        return Ok(());
+    }
+    if path.contains(&name) {
+        return Err(AocError::parse_error(
+            input,
+            name,
+            "this workflow can send parts back to itself",
+        ));
+    }
+    path.push(name);
+    for rule in &workflows[name] {
+        let (Rule::Test { target, .. } | Rule::Target(target)) = rule;
+        if let Destination::Workflow(next) = target {
+            check_cycles(input, workflows, next, path, finished)?;
+        }
+    }
+    path.pop();
+    finished.insert(name);
+    Ok(())
+}
+
 #[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let (_, (workflows, input)) = parse_input(input).expect("Should be valid");
-    let res = input.into_iter().fold(0_u32, |acc, rating| {
-        if check_acceptance(&rating, &workflows) {
-            acc + rating.x + rating.m + rating.a + rating.s
+pub fn parse(input: &str) -> miette::Result<System<'_>, AocError> {
+    let system = finish(input, parse_input)?;
+    check_workflows(input, &system.workflows)?;
+    Ok(system)
+}
+
+#[tracing::instrument(skip(system))]
+pub fn solve(system: &System<'_>) -> miette::Result<String, AocError> {
+    let System { workflows, ratings } = system;
+    let res = ratings.iter().fold(0_u64, |acc, rating| {
+        if check_acceptance(rating, workflows) {
+            acc + [rating.x, rating.m, rating.a, rating.s]
+                .into_iter()
+                .map(u64::from)
+                .sum::<u64>()
         } else {
             acc
         }
@@ -220,6 +303,30 @@ pub fn process(input: &str) -> miette::Result<String, AocError> {
     Ok(res.to_string())
 }
 
+#[tracing::instrument]
+pub fn process(input: &str) -> miette::Result<String, AocError> {
+    solve(&parse(input)?)
+}
+
+pub struct Part1;
+
+impl Solution for Part1 {
+    const DAY: u8 = crate::DAY;
+    const PART: u8 = 1;
+    const TITLE: &'static str = crate::TITLE;
+
+    type Model<'a> = System<'a>;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Self::Model<'_>, AocError> {
+        parse(input)
+    }
+
+    fn solve(system: &Self::Model<'_>, _params: &Self::Params) -> Result<String, AocError> {
+        solve(system)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -246,4 +353,13 @@ hdj{m>838:A,pv}
         assert_eq!("19114", process(input)?);
         Ok(())
     }
+
+    #[test]
+    fn test_workflow_cycle() {
+        let input = "in{x>10:px,A}
+px{m<5:in,R}
+
+{x=787,m=2,a=1222,s=2876}";
+        assert!(matches!(process(input), Err(AocError::ParseError { .. })));
+    }
 }
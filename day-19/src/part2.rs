@@ -1,4 +1,6 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+
+use aoc_core::{parse::finish, Solution};
 
 use crate::custom_error::AocError;
 
@@ -10,7 +12,7 @@ use nom::sequence::{delimited, preceded};
 use nom::{IResult, Parser};
 
 #[derive(PartialEq, Eq, Hash, Debug)]
-enum Part {
+pub enum Part {
     Xtremly,
     Musical,
     Aerodynamic,
@@ -18,20 +20,20 @@ enum Part {
 }
 
 #[derive(PartialEq, Eq, Hash, Debug, Copy, Clone)]
-enum Destination<'a> {
+pub enum Destination<'a> {
     Workflow(&'a str),
     Accepted,
     Rejected,
 }
 
 #[derive(PartialEq, Eq, Hash, Debug)]
-enum Condition {
+pub enum Condition {
     Greater,
     Lower,
 }
 
 #[derive(PartialEq, Eq, Hash, Debug)]
-enum Rule<'a> {
+pub enum Rule<'a> {
     Test {
         part: Part,
         target: Destination<'a>,
@@ -41,7 +43,7 @@ enum Rule<'a> {
     Target(Destination<'a>),
 }
 #[derive(Copy, Clone, Debug)]
-struct RatingRanges {
+pub struct RatingRanges {
     x: (u64, u64),
     m: (u64, u64),
     a: (u64, u64),
@@ -50,32 +52,31 @@ struct RatingRanges {
 
 impl RatingRanges {
     fn sum(&self) -> u64 {
-        (self.x.1 - self.x.0 + 1)
-            * (self.m.1 - self.m.0 + 1)
-            * (self.a.1 - self.a.0 + 1)
-            * (self.s.1 - self.s.0 + 1)
+        // an empty range has its high end below the low end
+        let len = |(low, high): (u64, u64)| (high + 1).saturating_sub(low);
+        len(self.x) * len(self.m) * len(self.a) * len(self.s)
     }
 
     fn adjust_high_range(&mut self, part: &Part, value: u64) {
         match part {
-            Part::Xtremly => self.x.1 = value,
-            Part::Musical => self.m.1 = value,
-            Part::Aerodynamic => self.a.1 = value,
-            Part::Shiny => self.s.1 = value,
+            Part::Xtremly => self.x.1 = self.x.1.min(value),
+            Part::Musical => self.m.1 = self.m.1.min(value),
+            Part::Aerodynamic => self.a.1 = self.a.1.min(value),
+            Part::Shiny => self.s.1 = self.s.1.min(value),
         }
     }
 
     fn adjust_low_range(&mut self, part: &Part, value: u64) {
         match part {
-            Part::Xtremly => self.x.0 = value,
-            Part::Musical => self.m.0 = value,
-            Part::Aerodynamic => self.a.0 = value,
-            Part::Shiny => self.s.0 = value,
+            Part::Xtremly => self.x.0 = self.x.0.max(value),
+            Part::Musical => self.m.0 = self.m.0.max(value),
+            Part::Aerodynamic => self.a.0 = self.a.0.max(value),
+            Part::Shiny => self.s.0 = self.s.0.max(value),
         }
     }
 }
 
-fn parse_destination(input: &str) -> IResult<&str, Rule> {
+fn parse_destination(input: &str) -> IResult<&str, Rule<'_>> {
     let (input, destination) = alt((
         tag("A").map(|_| Destination::Accepted),
         tag("R").map(|_| Destination::Rejected),
@@ -86,7 +87,7 @@ fn parse_destination(input: &str) -> IResult<&str, Rule> {
     Ok((input, Rule::Target(destination)))
 }
 
-fn parse_rule_test(input: &str) -> IResult<&str, Rule> {
+fn parse_rule_test(input: &str) -> IResult<&str, Rule<'_>> {
     let (input, part) = alt((
         complete::char('a').map(|_| Part::Aerodynamic),
         complete::char('x').map(|_| Part::Xtremly),
@@ -121,7 +122,7 @@ fn parse_rule_test(input: &str) -> IResult<&str, Rule> {
     ))
 }
 
-fn parse_workflow(input: &str) -> IResult<&str, (&str, Vec<Rule>)> {
+fn parse_workflow(input: &str) -> IResult<&str, (&str, Vec<Rule<'_>>)> {
     let (input, id) = alpha1(input)?;
     let (input, rules) = delimited(
         complete::char('{'),
@@ -144,7 +145,7 @@ fn parse_workflows(input: &str) -> IResult<&str, HashMap<&str, Vec<Rule<'_>>>> {
     Ok((input, workflows_map))
 }
 
-fn parse_input(input: &str) -> IResult<&str, HashMap<&str, Vec<Rule>>> {
+fn parse_input(input: &str) -> IResult<&str, HashMap<&str, Vec<Rule<'_>>>> {
     let (_, workflows_input) = alt((take_until("\r\n\r\n"), take_until("\n\n"))).parse(input)?;
 
     let (input, workflows) = parse_workflows(workflows_input)?;
@@ -174,7 +175,7 @@ fn get_ranges<'a>(
                         get_ranges(res, new_rating_range, map, *target);
                     }
                     Condition::Lower => {
-                        new_rating_range.adjust_high_range(part, *value as u64 - 1);
+                        new_rating_range.adjust_high_range(part, (*value as u64).saturating_sub(1));
                         range.adjust_low_range(part, *value as u64);
                         get_ranges(res, new_rating_range, map, *target);
                     }
@@ -187,9 +188,83 @@ fn get_ranges<'a>(
     }
 }
 
+/// Checks the `in` workflow exists, every workflow ends in an unconditional rule,
+/// every rule sends the parts to a known workflow and no part can go round in circles
+fn check_workflows(input: &str, workflows: &HashMap<&str, Vec<Rule<'_>>>) -> Result<(), AocError> {
+    if !workflows.contains_key("in") {
+        return Err(AocError::invalid_input("there is no `in` workflow"));
+    }
+    if let Some((name, _)) = workflows
+        .iter()
+        .find(|(_, rules)| !matches!(rules.last(), Some(Rule::Target(_))))
+    {
+        return Err(AocError::parse_error(
+            input,
+            name,
+            "the last rule of a workflow can not have a condition",
+        ));
+    }
+    let targets = workflows.values().flatten().map(|rule| match rule {
+        Rule::Test { target, .. } => target,
+        Rule::Target(target) => target,
+    });
+    for target in targets {
+        if let Destination::Workflow(name) = target {
+            if !workflows.contains_key(name) {
+                return Err(AocError::parse_error(
+                    input,
+                    name,
+                    "this workflow is never defined",
+                ));
+            }
+        }
+    }
+    let mut finished = HashSet::new();
+    for name in workflows.keys() {
+        check_cycles(input, workflows, name, &mut Vec::new(), &mut finished)?;
+    }
+    Ok(())
+}
+
+/// Depth first search from `name`, `path` holds the workflows leading to it
+fn check_cycles<'a>(
+    input: &str,
+    workflows: &HashMap<&'a str, Vec<Rule<'a>>>,
+    name: &'a str,
+    path: &mut Vec<&'a str>,
+    finished: &mut HashSet<&'a str>,
+) -> Result<(), AocError> {
+    if finished.contains(name) {
+        return Ok(());
+    }
+    if path.contains(&name) {
+        return Err(AocError::parse_error(
+            input,
+            name,
+            "this workflow can send parts back to itself",
+        ));
+    }
+    path.push(name);
+    for rule in &workflows[name] {
+        let (Rule::Test { target, .. } | Rule::Target(target)) = rule;
+        if let Destination::Workflow(next) = target {
+            check_cycles(input, workflows, next, path, finished)?;
+        }
+    }
+    path.pop();
+    finished.insert(name);
+    Ok(())
+}
+
 #[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let (_, workflows) = parse_input(input).expect("Should be valid");
+pub fn parse(input: &str) -> miette::Result<HashMap<&str, Vec<Rule<'_>>>, AocError> {
+    let workflows = finish(input, parse_input)?;
+    check_workflows(input, &workflows)?;
+    Ok(workflows)
+}
+
+#[tracing::instrument(skip(workflows))]
+pub fn solve(workflows: &HashMap<&str, Vec<Rule<'_>>>) -> miette::Result<String, AocError> {
     let ranges = RatingRanges {
         x: (1, 4000),
         m: (1, 4000),
@@ -197,10 +272,34 @@ pub fn process(input: &str) -> miette::Result<String, AocError> {
         s: (1, 4000),
     };
     let mut res = 0_u64;
-    get_ranges(&mut res, ranges, &workflows, Destination::Workflow("in"));
+    get_ranges(&mut res, ranges, workflows, Destination::Workflow("in"));
     Ok(res.to_string())
 }
 
+#[tracing::instrument]
+pub fn process(input: &str) -> miette::Result<String, AocError> {
+    solve(&parse(input)?)
+}
+
+pub struct Part2;
+
+impl Solution for Part2 {
+    const DAY: u8 = crate::DAY;
+    const PART: u8 = 2;
+    const TITLE: &'static str = crate::TITLE;
+
+    type Model<'a> = HashMap<&'a str, Vec<Rule<'a>>>;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Self::Model<'_>, AocError> {
+        parse(input)
+    }
+
+    fn solve(workflows: &Self::Model<'_>, _params: &Self::Params) -> Result<String, AocError> {
+        solve(workflows)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,4 +1,7 @@
-use day_20::part2::process;
+use day_20::{
+    part2::{parse, solve},
+    Presses,
+};
 use miette::Context;
 
 #[cfg(feature = "dhat-heap")]
@@ -13,8 +16,15 @@ fn main() -> miette::Result<()> {
     #[cfg(not(feature = "dhat-heap"))]
     tracing_subscriber::fmt::init();
 
-    let file = include_str!("../../input2.txt");
-    let result = process(file).context("process part 2")?;
+    #[cfg(feature = "embedded-input")]
+    let (file, presses) = (
+        include_str!("../../input2.txt").to_string(),
+        Presses::default(),
+    );
+    #[cfg(not(feature = "embedded-input"))]
+    let (file, presses) = aoc_core::input::from_args_with_params::<Presses>(day_20::DAY, 2)?;
+    let model = parse(&file).context("parse part 2")?;
+    let result = solve(&model, &presses).context("solve part 2")?;
     println!("{}", result);
     Ok(())
 }
@@ -2,3 +2,14 @@ pub mod custom_error;
 
 pub mod part1;
 pub mod part2;
+
+pub const DAY: u8 = 20;
+pub const TITLE: &str = "Pulse Propagation";
+
+aoc_core::params! {
+    /// How often the button is pushed
+    pub struct Presses {
+        /// Button pushes, each one waits for every pulse to be handled
+        presses: u32 = 1000,
+    }
+}
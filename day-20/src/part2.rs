@@ -1,24 +1,26 @@
 use std::collections::{HashMap, VecDeque};
 
 use nom::{
-    branch::alt,
     bytes::complete::tag,
-    character::complete::{self, alpha1, line_ending},
+    character::complete::{alpha1, line_ending, one_of},
+    combinator::opt,
     multi::separated_list1,
     sequence::{pair, separated_pair},
     IResult, Parser,
 };
 
-use crate::custom_error::AocError;
+use aoc_core::{parse::finish, Solution};
+
+use crate::{custom_error::AocError, Presses};
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
-enum Signal {
+pub enum Signal {
     Low,
     High,
 }
 
 #[derive(Debug, Clone)]
-enum Module<'a> {
+pub enum Module<'a> {
     FlipFlop { activated: bool },
     Conjunction { inputs: HashMap<&'a str, Signal> },
     Broadcaster,
@@ -39,55 +41,88 @@ impl SignalCounter {
     }
 }
 
-fn parse_input(input: &str) -> IResult<&str, HashMap<&str, (Module, Vec<&str>)>> {
-    let mut hashed_input: HashMap<&str, (Module, Vec<&str>)> = HashMap::new();
-    let (input, modules) = separated_list1(
+/// Every module by name together with the modules it sends pulses to
+pub type Modules<'a> = HashMap<&'a str, (Module<'a>, Vec<&'a str>)>;
+
+/// More pulses than this from one button push means the modules keep
+/// triggering each other and the push never settles
+const MAX_PULSES_PER_PUSH: usize = 1 << 20;
+
+/// A module line: the `%` or `&` prefix if there is one, the name and the
+/// modules it sends pulses to
+type Line<'a> = ((Option<char>, &'a str), Vec<&'a str>);
+
+fn parse_input(input: &str) -> IResult<&str, Vec<Line<'_>>> {
+    separated_list1(
         line_ending,
         separated_pair(
-            alt((
-                alpha1.map(|name| (name, Module::Broadcaster)),
-                pair(complete::char('%'), alpha1)
-                    .map(|(_, name)| (name, Module::FlipFlop { activated: false })),
-                pair(complete::char('&'), alpha1).map(|(_, name)| {
-                    (
-                        name,
-                        Module::Conjunction {
-                            inputs: HashMap::new(),
-                        },
-                    )
-                }),
-            )),
+            pair(opt(one_of("%&")), alpha1),
             tag(" -> "),
             separated_list1(tag(", "), alpha1),
         ),
     )
-    .parse(input)?;
+    .parse(input)
+}
+
+/// Only the `broadcaster` goes without a prefix, and every module is defined once.
+#[tracing::instrument]
+pub fn parse(input: &str) -> miette::Result<Modules<'_>, AocError> {
+    let mut modules: Modules = HashMap::new();
+    for ((prefix, name), destinations) in finish(input, parse_input)? {
+        let module = match (prefix, name) {
+            (None, "broadcaster") => Module::Broadcaster,
+            (Some(_), "broadcaster") => {
+                return Err(AocError::invalid_input(
+                    "the broadcaster takes no `%` or `&` prefix",
+                ))
+            }
+            (Some('%'), _) => Module::FlipFlop { activated: false },
+            (Some(_), _) => Module::Conjunction {
+                inputs: HashMap::new(),
+            },
+            (None, name) => {
+                return Err(AocError::invalid_input(format!(
+                    "module `{name}` needs a `%` or `&` prefix"
+                )))
+            }
+        };
+        if modules.insert(name, (module, destinations)).is_some() {
+            return Err(AocError::invalid_input(format!(
+                "module `{name}` is defined more than once"
+            )));
+        }
+    }
     modules
-        .into_iter()
-        .for_each(|((name, module), destinations)| {
-            hashed_input.insert(name, (module, destinations));
-        });
-    hashed_input
         .clone()
         .into_iter()
         .for_each(|(module_name, (_, destinations))| {
             destinations.iter().for_each(|dest| {
-                if let Some((Module::Conjunction { inputs }, _)) = hashed_input.get_mut(dest) {
+                if let Some((Module::Conjunction { inputs }, _)) = modules.get_mut(dest) {
                     inputs.insert(module_name, Signal::Low);
                 }
             })
         });
-    Ok((input, hashed_input))
+    Ok(modules)
 }
 
-#[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let (_, mut modules_map) = parse_input(input).expect("Should be valid");
+#[tracing::instrument(skip(modules_map))]
+pub fn solve(
+    modules_map: &Modules<'_>,
+    &Presses { presses }: &Presses,
+) -> miette::Result<String, AocError> {
+    let mut modules_map = modules_map.clone();
     let mut signal_counter = SignalCounter { low: 0, high: 0 };
-    for _ in 0..1000 {
+    for _ in 0..presses {
         let mut queue = VecDeque::new();
         queue.push_back(("start", Signal::Low, "broadcaster"));
+        let mut pulses = 0;
         while let Some((sender, signal, module_name)) = queue.pop_front() {
+            pulses += 1;
+            if pulses > MAX_PULSES_PER_PUSH {
+                return Err(AocError::invalid_input(format!(
+                    "a button push did not settle after {MAX_PULSES_PER_PUSH} pulses"
+                )));
+            }
             signal_counter.add(&signal);
             if let Some((module_type, destinations)) = modules_map.get_mut(module_name) {
                 let mut signal_to_send = Signal::Low;
@@ -122,6 +157,30 @@ pub fn process(input: &str) -> miette::Result<String, AocError> {
     Ok((signal_counter.low * signal_counter.high).to_string())
 }
 
+#[tracing::instrument]
+pub fn process(input: &str, presses: &Presses) -> miette::Result<String, AocError> {
+    solve(&parse(input)?, presses)
+}
+
+pub struct Part2;
+
+impl Solution for Part2 {
+    const DAY: u8 = crate::DAY;
+    const PART: u8 = 2;
+    const TITLE: &'static str = crate::TITLE;
+
+    type Model<'a> = Modules<'a>;
+    type Params = Presses;
+
+    fn parse(input: &str) -> Result<Self::Model<'_>, AocError> {
+        parse(input)
+    }
+
+    fn solve(modules_map: &Self::Model<'_>, params: &Self::Params) -> Result<String, AocError> {
+        solve(modules_map, params)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -133,7 +192,7 @@ mod tests {
 %b -> c
 %c -> inv
 &inv -> a";
-        assert_eq!("32000000", process(input)?);
+        assert_eq!("32000000", process(input, &Presses::default())?);
         Ok(())
     }
 
@@ -144,7 +203,7 @@ mod tests {
 &inv -> b
 %b -> con
 &con -> output";
-        assert_eq!("11687500", process(input)?);
+        assert_eq!("11687500", process(input, &Presses::default())?);
         Ok(())
     }
 }
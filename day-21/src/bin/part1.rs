@@ -1,4 +1,7 @@
-use day_21::part1::process;
+use day_21::{
+    part1::{parse, solve},
+    Steps,
+};
 use miette::Context;
 
 #[cfg(feature = "dhat-heap")]
@@ -13,8 +16,15 @@ fn main() -> miette::Result<()> {
     #[cfg(not(feature = "dhat-heap"))]
     tracing_subscriber::fmt::init();
 
-    let file = include_str!("../../input1.txt");
-    let result = process(file, 64).context("process part 1")?;
+    #[cfg(feature = "embedded-input")]
+    let (file, steps) = (
+        include_str!("../../input1.txt").to_string(),
+        Steps::default(),
+    );
+    #[cfg(not(feature = "embedded-input"))]
+    let (file, steps) = aoc_core::input::from_args_with_params::<Steps>(day_21::DAY, 1)?;
+    let model = parse(&file).context("parse part 1")?;
+    let result = solve(&model, &steps).context("solve part 1")?;
     println!("{}", result);
     Ok(())
 }
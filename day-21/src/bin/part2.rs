@@ -1,4 +1,7 @@
-use day_21::part2::process;
+use day_21::{
+    part2::{parse, solve},
+    Steps,
+};
 use miette::Context;
 
 #[cfg(feature = "dhat-heap")]
@@ -13,8 +16,15 @@ fn main() -> miette::Result<()> {
     #[cfg(not(feature = "dhat-heap"))]
     tracing_subscriber::fmt::init();
 
-    let file = include_str!("../../input2.txt");
-    let result = process(file, 64).context("process part 2")?;
+    #[cfg(feature = "embedded-input")]
+    let (file, steps) = (
+        include_str!("../../input2.txt").to_string(),
+        Steps::default(),
+    );
+    #[cfg(not(feature = "embedded-input"))]
+    let (file, steps) = aoc_core::input::from_args_with_params::<Steps>(day_21::DAY, 2)?;
+    let model = parse(&file).context("parse part 2")?;
+    let result = solve(&model, &steps).context("solve part 2")?;
     println!("{}", result);
     Ok(())
 }
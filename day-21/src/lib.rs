@@ -2,3 +2,14 @@ pub mod custom_error;
 
 pub mod part1;
 pub mod part2;
+
+pub const DAY: u8 = 21;
+pub const TITLE: &str = "Step Counter";
+
+aoc_core::params! {
+    /// Number of steps the elf takes from the starting plot
+    pub struct Steps {
+        /// Steps taken from `S`
+        steps: u32 = 64,
+    }
+}
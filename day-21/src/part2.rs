@@ -7,16 +7,22 @@ use nom::{
     IResult, Parser,
 };
 
-use crate::custom_error::AocError;
+use aoc_core::{
+    parse::{finish, grid_of},
+    Solution,
+};
+
+use crate::{custom_error::AocError, Steps};
 
-#[derive(Debug, PartialEq, Eq)]
-enum Object {
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Object {
     Start,
     Plot,
     Rock,
 }
 
-struct Entry {
+#[derive(Debug, Clone)]
+pub struct Entry {
     object: Object,
     visited: bool,
 }
@@ -31,7 +37,7 @@ impl Entry {
 
     fn possible_neighbours_coordinates(
         (y, x): (usize, usize),
-        entries_map: &mut Vec<Vec<Entry>>,
+        entries_map: &mut [Vec<Entry>],
     ) -> Vec<(usize, usize)> {
         let mut res = Vec::new();
         if y > 0 && entries_map[y - 1][x].object != Object::Rock && !entries_map[y - 1][x].visited {
@@ -75,26 +81,32 @@ fn parse_input(input: &str) -> IResult<&str, Vec<Vec<Entry>>> {
 }
 
 #[tracing::instrument]
-pub fn process(input: &str, steps: u32) -> miette::Result<String, AocError> {
-    let (_, mut board) = parse_input(input).expect("Should be valid!");
+pub fn parse(input: &str) -> miette::Result<Vec<Vec<Entry>>, AocError> {
+    grid_of(input, "S.#")?;
+    finish(input, parse_input)
+}
+
+#[tracing::instrument(skip(board))]
+pub fn solve(board: &[Vec<Entry>], &Steps { steps }: &Steps) -> miette::Result<String, AocError> {
+    let mut board = board.to_vec();
     let start_coordinates = board
         .iter()
         .enumerate()
         .find_map(|(y, some_vec)| {
             some_vec.iter().enumerate().find_map(|(x, object)| {
                 if object.object == Object::Start {
-                    Some((x, y))
+                    Some((y, x))
                 } else {
                     None
                 }
             })
         })
-        .expect("Should exist");
+        .ok_or_else(|| AocError::invalid_input("the garden has no starting point `S`"))?;
     let mut stack = VecDeque::new();
     stack.push_back((start_coordinates, 0_u32));
     let mut res = 0;
     while let Some(((y, x), curr_steps)) = stack.pop_front() {
-        if curr_steps > steps + 1 {
+        if curr_steps > steps.saturating_add(1) {
             break;
         }
         if curr_steps % 2 == 0 {
@@ -108,6 +120,30 @@ pub fn process(input: &str, steps: u32) -> miette::Result<String, AocError> {
     Ok((res - 1).to_string())
 }
 
+#[tracing::instrument]
+pub fn process(input: &str, steps: &Steps) -> miette::Result<String, AocError> {
+    solve(&parse(input)?, steps)
+}
+
+pub struct Part2;
+
+impl Solution for Part2 {
+    const DAY: u8 = crate::DAY;
+    const PART: u8 = 2;
+    const TITLE: &'static str = crate::TITLE;
+
+    type Model<'a> = Vec<Vec<Entry>>;
+    type Params = Steps;
+
+    fn parse(input: &str) -> Result<Self::Model<'_>, AocError> {
+        parse(input)
+    }
+
+    fn solve(board: &Self::Model<'_>, params: &Self::Params) -> Result<String, AocError> {
+        solve(board, params)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -147,7 +183,7 @@ mod tests {
 .##.#.####..##.#.####..##.#.####.
 .##..##.##..##..##.##..##..##.##.
 .................................";
-        assert_eq!("50", process(input, 10)?);
+        assert_eq!("50", process(input, &Steps { steps: 10 })?);
         Ok(())
     }
 }
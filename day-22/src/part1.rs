@@ -7,13 +7,18 @@ use nom::{
     IResult, Parser,
 };
 
+use aoc_core::{parse::finish, Solution};
+
 use crate::custom_error::AocError;
 
+/// The biggest space the bricks are dropped into.
+const MAX_CELLS: u64 = 1 << 24;
+
 #[derive(Debug)]
-struct Brick {
-    x_range: (u32, u32),
-    y_range: (u32, u32),
-    z_range: (u32, u32),
+pub struct Brick {
+    pub x_range: (u32, u32),
+    pub y_range: (u32, u32),
+    pub z_range: (u32, u32),
 }
 
 fn parse_input(input: &str) -> IResult<&str, Vec<Brick>> {
@@ -35,7 +40,7 @@ fn parse_input(input: &str) -> IResult<&str, Vec<Brick>> {
         .map(|((x_min, y_min, z_min), (x_max, y_max, z_max))| Brick {
             x_range: (x_min, x_max),
             y_range: (y_min, y_max),
-            z_range: (z_min - 1, z_max - 1),
+            z_range: (z_min, z_max),
         }),
     )
     .parse(input)?;
@@ -43,39 +48,83 @@ fn parse_input(input: &str) -> IResult<&str, Vec<Brick>> {
     Ok((input, bricks))
 }
 
+/// Checks the ends of every brick are in order and above the ground, and moves
+/// the bricks one down so the lowest possible z is 0.
+fn lower_to_ground(mut bricks: Vec<Brick>) -> Result<Vec<Brick>, AocError> {
+    for (idx, brick) in bricks.iter_mut().enumerate() {
+        if brick.x_range.0 > brick.x_range.1
+            || brick.y_range.0 > brick.y_range.1
+            || brick.z_range.0 > brick.z_range.1
+        {
+            return Err(AocError::invalid_input(format!(
+                "brick {} ends before it starts",
+                idx + 1
+            )));
+        }
+        if brick.z_range.0 == 0 {
+            return Err(AocError::invalid_input(format!(
+                "brick {} is in the ground, z starts at 1",
+                idx + 1
+            )));
+        }
+        brick.z_range = (brick.z_range.0 - 1, brick.z_range.1 - 1);
+    }
+    let cells = bricks
+        .iter()
+        .fold((0_u64, 0_u64, 0_u64), |(x, y, z), brick| {
+            (
+                x.max(brick.x_range.1 as u64 + 1),
+                y.max(brick.y_range.1 as u64 + 1),
+                z.max(brick.z_range.1 as u64 + 1),
+            )
+        });
+    if cells.0.saturating_mul(cells.1).saturating_mul(cells.2) > MAX_CELLS {
+        return Err(AocError::invalid_input(format!(
+            "the bricks span {}x{}x{} cells, at most {MAX_CELLS} can be simulated",
+            cells.0, cells.1, cells.2
+        )));
+    }
+    Ok(bricks)
+}
+
 fn get_supporting_bricks_and_fallen_ammount_of_z(
     brick: &Brick,
-    space: &Vec<Vec<Vec<u32>>>,
+    space: &[Vec<Vec<u32>>],
     fallen_by: &mut u32,
 ) -> HashSet<u32> {
     let mut supported_ids = HashSet::new();
-    if brick.z_range.0 <= *fallen_by {
-        return supported_ids;
-    }
-    for y in brick.y_range.0..brick.y_range.1 + 1 {
-        for x in brick.x_range.0..brick.x_range.1 + 1 {
-            if space[(brick.z_range.0 as usize - *fallen_by as usize) - 1][y as usize][x as usize]
-                != 0
-            {
-                supported_ids.insert(
-                    space[(brick.z_range.0 as usize - *fallen_by as usize) - 1][y as usize]
-                        [x as usize],
-                );
+    while brick.z_range.0 > *fallen_by {
+        for y in brick.y_range.0..brick.y_range.1 + 1 {
+            for x in brick.x_range.0..brick.x_range.1 + 1 {
+                if space[(brick.z_range.0 as usize - *fallen_by as usize) - 1][y as usize]
+                    [x as usize]
+                    != 0
+                {
+                    supported_ids.insert(
+                        space[(brick.z_range.0 as usize - *fallen_by as usize) - 1][y as usize]
+                            [x as usize],
+                    );
+                }
             }
         }
+        if !supported_ids.is_empty() {
+            break;
+        }
+        *fallen_by += 1;
     }
-    if !supported_ids.is_empty() {
-        return supported_ids;
-    }
-    *fallen_by += 1;
-    get_supporting_bricks_and_fallen_ammount_of_z(brick, space, fallen_by)
+    supported_ids
 }
 
 #[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let (_, mut bricks) = parse_input(input).expect("Should be valid");
+pub fn parse(input: &str) -> miette::Result<Vec<Brick>, AocError> {
+    lower_to_ground(finish(input, parse_input)?)
+}
+
+#[tracing::instrument(skip(bricks))]
+pub fn solve(bricks: &[Brick]) -> miette::Result<String, AocError> {
+    let mut bricks = bricks.iter().collect::<Vec<&Brick>>();
     let bricks_ammount = bricks.len();
-    bricks.sort_unstable_by(|a, b| a.z_range.cmp(&b.z_range));
+    bricks.sort_unstable_by_key(|brick| brick.z_range);
     let mut supporting: HashMap<u32, HashSet<u32>> = HashMap::new();
     let (x_max, y_max, z_max) = bricks.iter().fold((0, 0, 0), |(x, y, z), k| {
         (x.max(k.x_range.1), y.max(k.y_range.1), z.max(k.z_range.1))
@@ -84,7 +133,7 @@ pub fn process(input: &str) -> miette::Result<String, AocError> {
     for (id, brick) in bricks.into_iter().enumerate() {
         let mut fallen_by = 0;
         let supported_by =
-            get_supporting_bricks_and_fallen_ammount_of_z(&brick, &space, &mut fallen_by);
+            get_supporting_bricks_and_fallen_ammount_of_z(brick, &space, &mut fallen_by);
         for brick in supported_by {
             supporting
                 .entry(id as u32 + 1)
@@ -118,6 +167,30 @@ pub fn process(input: &str) -> miette::Result<String, AocError> {
     Ok(res.to_string())
 }
 
+#[tracing::instrument]
+pub fn process(input: &str) -> miette::Result<String, AocError> {
+    solve(&parse(input)?)
+}
+
+pub struct Part1;
+
+impl Solution for Part1 {
+    const DAY: u8 = crate::DAY;
+    const PART: u8 = 1;
+    const TITLE: &'static str = crate::TITLE;
+
+    type Model<'a> = Vec<Brick>;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Self::Model<'_>, AocError> {
+        parse(input)
+    }
+
+    fn solve(bricks: &Self::Model<'_>, _params: &Self::Params) -> Result<String, AocError> {
+        solve(bricks)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -134,4 +207,16 @@ mod tests {
         assert_eq!("5", process(input)?);
         Ok(())
     }
+
+    #[test]
+    fn test_brick_in_the_ground() {
+        assert!(matches!(
+            parse("1,0,0~1,2,1"),
+            Err(AocError::InvalidInput(message)) if message == "brick 1 is in the ground, z starts at 1"
+        ));
+        assert!(matches!(
+            parse("0,0,1~0,0,4294967295"),
+            Err(AocError::InvalidInput(_))
+        ));
+    }
 }
@@ -5,10 +5,15 @@ use nom::{
     IResult, Parser,
 };
 
+use aoc_core::{
+    parse::{finish, grid_of},
+    Solution,
+};
+
 use crate::custom_error::AocError;
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
-enum Entry {
+pub enum Entry {
     Path,
     Forest,
     EastSlope,
@@ -34,7 +39,7 @@ fn parse_input(input: &str) -> IResult<&str, Vec<Vec<Entry>>> {
     Ok((input, map))
 }
 
-fn extract_neighbours((y, x): &(usize, usize), map: &Vec<Vec<Entry>>) -> Vec<(usize, usize)> {
+fn extract_neighbours((y, x): &(usize, usize), map: &[Vec<Entry>]) -> Vec<(usize, usize)> {
     let mut neighbours: Vec<(usize, usize)> = Vec::new();
     if *y > 0 && map[*y - 1][*x] != Entry::Forest && map[*y - 1][*x] != Entry::SouthSlope {
         neighbours.push((*y - 1, *x));
@@ -64,7 +69,7 @@ fn dfs(
     (mut y, mut x): (usize, usize),
 ) {
     let mut neighbours = extract_neighbours(&(y, x), &map);
-    while neighbours.len() > 0 {
+    while !neighbours.is_empty() {
         current_moves += 1;
         map[y][x] = Entry::Forest;
         if neighbours.len() == 1 {
@@ -81,14 +86,50 @@ fn dfs(
 }
 
 #[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let (_, map) = parse_input(input).expect("Should be valid");
+pub fn parse(input: &str) -> miette::Result<Vec<Vec<Entry>>, AocError> {
+    let lines = grid_of(input, ".#^>v<")?;
+    if lines[0].get(1..2) != Some(".") {
+        return Err(AocError::parse_error(
+            input,
+            lines[0],
+            "expected the start at the second column",
+        ));
+    }
+    finish(input, parse_input)
+}
+
+#[tracing::instrument(skip(map))]
+pub fn solve(map: &[Vec<Entry>]) -> miette::Result<String, AocError> {
     let mut res = 0;
-    dfs(&mut res, 0, map, (0, 1));
+    dfs(&mut res, 0, map.to_vec(), (0, 1));
 
     Ok(res.to_string())
 }
 
+#[tracing::instrument]
+pub fn process(input: &str) -> miette::Result<String, AocError> {
+    solve(&parse(input)?)
+}
+
+pub struct Part1;
+
+impl Solution for Part1 {
+    const DAY: u8 = crate::DAY;
+    const PART: u8 = 1;
+    const TITLE: &'static str = crate::TITLE;
+
+    type Model<'a> = Vec<Vec<Entry>>;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Self::Model<'_>, AocError> {
+        parse(input)
+    }
+
+    fn solve(map: &Self::Model<'_>, _params: &Self::Params) -> Result<String, AocError> {
+        solve(map)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
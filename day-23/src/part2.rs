@@ -5,10 +5,15 @@ use nom::{
     IResult, Parser,
 };
 
+use aoc_core::{
+    parse::{finish, grid_of},
+    Solution,
+};
+
 use crate::custom_error::AocError;
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
-enum Entry {
+pub enum Entry {
     Path,
     Forest,
 }
@@ -26,7 +31,7 @@ fn parse_input(input: &str) -> IResult<&str, Vec<Vec<Entry>>> {
     Ok((input, map))
 }
 
-fn extract_neighbours((y, x): &(usize, usize), map: &Vec<Vec<Entry>>) -> Vec<(usize, usize)> {
+fn extract_neighbours((y, x): &(usize, usize), map: &[Vec<Entry>]) -> Vec<(usize, usize)> {
     let mut neighbours: Vec<(usize, usize)> = Vec::new();
     if *y > 0 && map[*y - 1][*x] != Entry::Forest {
         neighbours.push((*y - 1, *x));
@@ -51,7 +56,7 @@ fn dfs(
     end_coordinates: &(usize, usize),
 ) {
     let mut neighbours = extract_neighbours(&(y, x), &map);
-    while neighbours.len() > 0 {
+    while !neighbours.is_empty() {
         current_moves += 1;
         map[y][x] = Entry::Forest;
         if neighbours.len() == 1 {
@@ -76,15 +81,51 @@ fn dfs(
 }
 
 #[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let (_, map) = parse_input(input).expect("Should be valid");
+pub fn parse(input: &str) -> miette::Result<Vec<Vec<Entry>>, AocError> {
+    let lines = grid_of(input, ".#^>v<")?;
+    if lines[0].get(1..2) != Some(".") {
+        return Err(AocError::parse_error(
+            input,
+            lines[0],
+            "expected the start at the second column",
+        ));
+    }
+    finish(input, parse_input)
+}
+
+#[tracing::instrument(skip(map))]
+pub fn solve(map: &[Vec<Entry>]) -> miette::Result<String, AocError> {
     let end_coordinates = (map.len() - 1, map[0].len() - 2);
     let mut res = 0;
-    dfs(&mut res, 0, map, (0, 1), &end_coordinates);
+    dfs(&mut res, 0, map.to_vec(), (0, 1), &end_coordinates);
 
     Ok(res.to_string())
 }
 
+#[tracing::instrument]
+pub fn process(input: &str) -> miette::Result<String, AocError> {
+    solve(&parse(input)?)
+}
+
+pub struct Part2;
+
+impl Solution for Part2 {
+    const DAY: u8 = crate::DAY;
+    const PART: u8 = 2;
+    const TITLE: &'static str = crate::TITLE;
+
+    type Model<'a> = Vec<Vec<Entry>>;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Self::Model<'_>, AocError> {
+        parse(input)
+    }
+
+    fn solve(map: &Self::Model<'_>, _params: &Self::Params) -> Result<String, AocError> {
+        solve(map)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,4 +1,4 @@
-use day_24::part1::process;
+use day_24::part1::{parse, solve, TestArea};
 use miette::Context;
 
 #[cfg(feature = "dhat-heap")]
@@ -13,8 +13,15 @@ fn main() -> miette::Result<()> {
     #[cfg(not(feature = "dhat-heap"))]
     tracing_subscriber::fmt::init();
 
-    let file = include_str!("../../input1.txt");
-    let result = process(file, (200000000000000.0, 400000000000000.0)).context("process part 1")?;
+    #[cfg(feature = "embedded-input")]
+    let (file, area) = (
+        include_str!("../../input1.txt").to_string(),
+        TestArea::default(),
+    );
+    #[cfg(not(feature = "embedded-input"))]
+    let (file, area) = aoc_core::input::from_args_with_params::<TestArea>(day_24::DAY, 1)?;
+    let model = parse(&file).context("parse part 1")?;
+    let result = solve(&model, &area).context("solve part 1")?;
     println!("{}", result);
     Ok(())
 }
@@ -6,17 +6,19 @@ use nom::{
     IResult, Parser,
 };
 
+use aoc_core::{parse::finish, Solution};
+
 use crate::custom_error::AocError;
 
 #[derive(Debug)]
 #[warn(dead_code)]
-struct Hailstone {
-    px: i64,
-    py: i64,
-    pz: i64,
-    vx: i64,
-    vy: i64,
-    vz: i64,
+pub struct Hailstone {
+    pub px: i64,
+    pub py: i64,
+    pub pz: i64,
+    pub vx: i64,
+    pub vy: i64,
+    pub vz: i64,
 }
 
 impl Hailstone {
@@ -30,6 +32,15 @@ impl Hailstone {
             vz,
         }
     }
+
+    /// Whether the hailstone is at `x` now or will get there
+    fn reaches(&self, x: f64) -> bool {
+        match self.vx.signum() {
+            -1 => x <= self.px as f64,
+            1 => x >= self.px as f64,
+            _ => true,
+        }
+    }
 }
 
 fn parse_input(input: &str) -> IResult<&str, Vec<Hailstone>> {
@@ -55,56 +66,54 @@ fn parse_input(input: &str) -> IResult<&str, Vec<Hailstone>> {
     Ok((input, hailstones))
 }
 
-// https://paulbourke.net/geometry/pointlineplane/
-fn calculate_intersection(
-    (p1, p2, p3, p4): ((i64, i64), (i64, i64), (i64, i64), (i64, i64)),
-) -> Option<(f64, f64)> {
-    if (p1.0 == p2.0 && p1.1 == p2.1) || (p3.0 == p4.0 && p3.1 == p4.1) {
+// https://paulbourke.net/geometry/pointlineplane/ with the second point of
+// each line one step along the velocity. Computed in i128 as the products of
+// far away hailstones do not fit in an i64.
+fn calculate_intersection(a: &Hailstone, b: &Hailstone) -> Option<(f64, f64)> {
+    if (a.vx, a.vy) == (0, 0) || (b.vx, b.vy) == (0, 0) {
         return None;
     }
 
-    let denominator = ((p4.1 - p3.1) * (p2.0 - p1.0) - (p4.0 - p3.0) * (p2.1 - p1.1)) as f64;
+    let denominator = b.vy as i128 * a.vx as i128 - b.vx as i128 * a.vy as i128;
 
-    if denominator == 0.0 {
+    if denominator == 0 {
         return None;
     }
 
-    let ua = ((p4.0 - p3.0) * (p1.1 - p3.1) - (p4.1 - p3.1) * (p1.0 - p3.0)) as f64 / denominator;
+    let left = b.vx as i128 * (a.py as i128 - b.py as i128);
+    let right = b.vy as i128 * (a.px as i128 - b.px as i128);
+    let numerator = left
+        .checked_sub(right)
+        .map_or(left as f64 - right as f64, |numerator| numerator as f64);
+    let ua = numerator / denominator as f64;
 
     Some((
-        p1.0 as f64 + ua * (p2.0 - p1.0) as f64,
-        p1.1 as f64 + ua * (p2.1 - p1.1) as f64,
+        a.px as f64 + ua * a.vx as f64,
+        a.py as f64 + ua * a.vy as f64,
     ))
 }
 
 #[tracing::instrument]
-pub fn process(input: &str, (left, right): (f64, f64)) -> miette::Result<String, AocError> {
-    let (_, hailstones) = parse_input(input).expect("Should be valid");
+pub fn parse(input: &str) -> miette::Result<Vec<Hailstone>, AocError> {
+    finish(input, parse_input)
+}
+
+#[tracing::instrument(skip(hailstones))]
+pub fn solve(
+    hailstones: &[Hailstone],
+    &TestArea { min, max }: &TestArea,
+) -> miette::Result<String, AocError> {
     let mut res = 0;
     for i in 0..hailstones.len() {
         for j in i..hailstones.len() {
             let (hailstone_a, hailstone_b) = (&hailstones[i], &hailstones[j]);
-            let point_1 = (hailstone_a.px, hailstone_a.py);
-            let point_2 = (
-                hailstone_a.px + hailstone_a.vx,
-                hailstone_a.py + hailstone_a.vy,
-            );
-            let point_3 = (hailstone_b.px, hailstone_b.py);
-            let point_4 = (
-                hailstone_b.px + hailstone_b.vx,
-                hailstone_b.py + hailstone_b.vy,
-            );
-            if let Some((x_inct, y_inct)) =
-                calculate_intersection((point_1, point_2, point_3, point_4))
-            {
-                if !(hailstone_a.vx.is_negative() && x_inct > hailstone_a.px as f64)
-                    && !(hailstone_a.vx.is_positive() && x_inct < hailstone_a.px as f64)
-                    && !(hailstone_b.vx.is_negative() && x_inct > hailstone_b.px as f64)
-                    && !(hailstone_b.vx.is_positive() && x_inct < hailstone_b.px as f64)
-                    && x_inct >= left
-                    && x_inct <= right
-                    && y_inct >= left
-                    && y_inct <= right
+            if let Some((x_inct, y_inct)) = calculate_intersection(hailstone_a, hailstone_b) {
+                if hailstone_a.reaches(x_inct)
+                    && hailstone_b.reaches(x_inct)
+                    && x_inct >= min
+                    && x_inct <= max
+                    && y_inct >= min
+                    && y_inct <= max
                 {
                     res += 1;
                 }
@@ -114,6 +123,40 @@ pub fn process(input: &str, (left, right): (f64, f64)) -> miette::Result<String,
     Ok(res.to_string())
 }
 
+#[tracing::instrument]
+pub fn process(input: &str, area: &TestArea) -> miette::Result<String, AocError> {
+    solve(&parse(input)?, area)
+}
+
+aoc_core::params! {
+    /// The square both X and Y of a crossing have to fall into
+    pub struct TestArea {
+        /// Smallest X and Y of a crossing
+        min: f64 = 2e14,
+        /// Largest X and Y of a crossing
+        max: f64 = 4e14,
+    }
+}
+
+pub struct Part1;
+
+impl Solution for Part1 {
+    const DAY: u8 = crate::DAY;
+    const PART: u8 = 1;
+    const TITLE: &'static str = crate::TITLE;
+
+    type Model<'a> = Vec<Hailstone>;
+    type Params = TestArea;
+
+    fn parse(input: &str) -> Result<Self::Model<'_>, AocError> {
+        parse(input)
+    }
+
+    fn solve(hailstones: &Self::Model<'_>, params: &Self::Params) -> Result<String, AocError> {
+        solve(hailstones, params)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -125,7 +168,16 @@ mod tests {
 20, 25, 34 @ -2, -2, -4
 12, 31, 28 @ -1, -2, -1
 20, 19, 15 @ 1, -5, -3";
-        assert_eq!("2", process(input, (7.0, 27.0))?);
+        assert_eq!(
+            "2",
+            process(
+                input,
+                &TestArea {
+                    min: 7.0,
+                    max: 27.0
+                }
+            )?
+        );
         Ok(())
     }
 }
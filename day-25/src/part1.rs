@@ -8,6 +8,10 @@ use nom::{
     IResult, Parser,
 };
 
+use itertools::Itertools;
+
+use aoc_core::{parse::finish, Solution};
+
 use crate::custom_error::AocError;
 
 fn parse_components(input: &str) -> IResult<&str, Vec<&str>> {
@@ -51,28 +55,105 @@ fn parse_input(input: &str) -> IResult<&str, HashMap<String, HashSet<String>>> {
 }
 
 #[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let (_, mut graph) = parse_input(input).expect("Should work");
-    while graph.len() > 2 {
-        let v = graph.keys().next().expect("Exists").clone();
-        let (v, mut v_values) = graph.remove_entry(&v).expect("W entry should exist");
-        let w = v_values.iter().next().expect("W value should exist").clone();
-        let (w, w_values) = graph.remove_entry(&w).expect("V values should exist");
-        let new_vw = format!("{}:{}", v, w);
-        for set in graph.values_mut() {
-            if set.remove(&v) {
-                set.insert(new_vw.clone());
+pub fn parse(input: &str) -> miette::Result<HashMap<String, HashSet<String>>, AocError> {
+    finish(input, parse_input)
+}
+
+/// Xorshift generator with a fixed seed, the contraction only needs a shuffled
+/// edge order and a fixed seed keeps the answer reproducible between runs
+struct XorShift(u64);
+
+impl XorShift {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+}
+
+fn find_root(parents: &mut [usize], mut node: usize) -> usize {
+    while parents[node] != node {
+        parents[node] = parents[parents[node]];
+        node = parents[node];
+    }
+    node
+}
+
+const MAX_CONTRACTIONS: usize = 10_000;
+
+#[tracing::instrument(skip(graph))]
+pub fn solve(graph: &HashMap<String, HashSet<String>>) -> miette::Result<String, AocError> {
+    let names = graph.keys().sorted().collect::<Vec<&String>>();
+    let index = names
+        .iter()
+        .enumerate()
+        .map(|(idx, name)| (name.as_str(), idx))
+        .collect::<HashMap<&str, usize>>();
+    let mut edges = graph
+        .iter()
+        .flat_map(|(from, neighbours)| neighbours.iter().map(move |to| (from, to)))
+        .filter(|(from, to)| from < to)
+        .map(|(from, to)| (index[from.as_str()], index[to.as_str()]))
+        .sorted()
+        .collect::<Vec<(usize, usize)>>();
+    let mut rng = XorShift(0x2545_f491_4f6c_dd1d);
+    // Karger's contraction, repeated until the two groups are joined by exactly three wires
+    for _ in 0..MAX_CONTRACTIONS {
+        for i in (1..edges.len()).rev() {
+            edges.swap(i, rng.next() as usize % (i + 1));
+        }
+        let mut parents = (0..names.len()).collect::<Vec<usize>>();
+        let mut groups = names.len();
+        for &(from, to) in &edges {
+            if groups == 2 {
+                break;
             }
-            if set.remove(&w) {
-                set.insert(new_vw.clone());
+            let (from, to) = (find_root(&mut parents, from), find_root(&mut parents, to));
+            if from != to {
+                parents[from] = to;
+                groups -= 1;
             }
         }
-        v_values.extend(w_values);
-        v_values.remove(&w);
-        v_values.remove(&v);
-        graph.insert(new_vw, v_values);
+        let cut = edges
+            .iter()
+            .filter(|&&(from, to)| find_root(&mut parents, from) != find_root(&mut parents, to))
+            .count();
+        if groups == 2 && cut == 3 {
+            let root = find_root(&mut parents, 0);
+            let first_group = (0..names.len())
+                .filter(|&node| find_root(&mut parents, node) == root)
+                .count();
+            return Ok((first_group * (names.len() - first_group)).to_string());
+        }
+    }
+    Err(AocError::invalid_input(
+        "no way to split the components by cutting three wires",
+    ))
+}
+
+#[tracing::instrument]
+pub fn process(input: &str) -> miette::Result<String, AocError> {
+    solve(&parse(input)?)
+}
+
+pub struct Part1;
+
+impl Solution for Part1 {
+    const DAY: u8 = crate::DAY;
+    const PART: u8 = 1;
+    const TITLE: &'static str = crate::TITLE;
+
+    type Model<'a> = HashMap<String, HashSet<String>>;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Self::Model<'_>, AocError> {
+        parse(input)
+    }
+
+    fn solve(graph: &Self::Model<'_>, _params: &Self::Params) -> Result<String, AocError> {
+        solve(graph)
     }
-    Ok(graph.values().map(|v| v.iter().next().expect("Should exist").split(":").count()).product::<usize>().to_string())
 }
 
 #[cfg(test)]
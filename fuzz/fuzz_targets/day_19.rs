@@ -0,0 +1,8 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    aoc_fuzz::parse_and_solve::<day_19::part1::Part1>(input);
+    aoc_fuzz::parse_and_solve::<day_19::part2::Part2>(input);
+});
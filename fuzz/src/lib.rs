@@ -0,0 +1,9 @@
+use aoc_core::Solution;
+
+/// Runs one part the way `Solution::process` does, any `AocError` is fine but a
+/// panic is a bug in the parser or the solution.
+pub fn parse_and_solve<S: Solution>(input: &str) {
+    if let Ok(model) = S::parse(input) {
+        let _ = S::solve(&model, &S::Params::default());
+    }
+}
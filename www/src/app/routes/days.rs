@@ -1,5 +1,8 @@
+use std::time::Duration;
+
 use leptos::*;
 use leptos_router::*;
+use serde::{Deserialize, Serialize};
 use tracing::*;
 
 #[derive(Params, PartialEq, Clone)]
@@ -8,11 +11,21 @@ struct Day {
     part: String,
 }
 
+/// What the server sends back for a submitted input
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub enum Solved {
+    Answer { answer: String, elapsed: Duration },
+    /// The `AocError` report rendered without colors
+    Failed(String),
+}
+
 #[tracing::instrument]
 #[component]
 pub fn SolutionPage() -> impl IntoView {
     let params = use_params::<Day>();
     let solve = create_server_action::<Solve>();
+    let solved = solve.value();
+    let pending = solve.pending();
 
     let Ok(day) = params.get() else {
         return view! {
@@ -29,6 +42,8 @@ pub fn SolutionPage() -> impl IntoView {
             <ActionForm class="text-ctp-text relative" action={solve}>
                 <input name="day" value={day.day} type="hidden" />
                 <input name="part" value={day.part} type="hidden" />
+                <label for="params" class="sr-only">Puzzle parameters</label>
+                <input type="text" name="params" id="params" class="mb-2 block w-full rounded-lg border-0 bg-transparent py-1.5 text-ctp-text ring-1 ring-inset ring-gray-300 placeholder:text-gray-400 focus:ring-2 focus:ring-teal-600 sm:text-sm" placeholder="Parameters, e.g. steps=6 (optional)" />
 
 
                 <div class="overflow-hidden rounded-lg shadow-sm ring-1 ring-inset ring-gray-300 focus-within:ring-2 focus-within:ring-teal-600">
@@ -51,6 +66,27 @@ pub fn SolutionPage() -> impl IntoView {
                 </div>
 
             </ActionForm>
+            <div class="col-span-3 text-ctp-text">
+                <Show when=move || pending.get()>
+                    <p>"Solving..."</p>
+                </Show>
+                {move || match solved.get() {
+                    None => ().into_view(),
+                    Some(Ok(Solved::Answer { answer, elapsed })) => view! {
+                        <p>"Answer: " <code class="text-ctp-green">{answer}</code></p>
+                        <p class="text-sm">{format!("solved in {elapsed:.2?}")}</p>
+                    }
+                    .into_view(),
+                    Some(Ok(Solved::Failed(report))) => view! {
+                        <pre class="text-ctp-red whitespace-pre-wrap text-sm">{report}</pre>
+                    }
+                    .into_view(),
+                    Some(Err(error)) => view! {
+                        <p class="text-ctp-red">{error.to_string()}</p>
+                    }
+                    .into_view(),
+                }}
+            </div>
             </div>
         </div>
     }
@@ -87,8 +123,43 @@ async fn solve(
     day: String,
     part: String,
     input: String,
-) -> Result<Vec<()>, ServerFnError> {
-    info!(day, part, input);
-    let posts = vec![];
-    Ok(posts)
+    params: String,
+) -> Result<Solved, ServerFnError> {
+    let (Ok(day), Ok(part)) = (day.parse::<u8>(), part.parse::<u8>()) else {
+        return Err(ServerFnError::Args(format!(
+            "day `{day}` part `{part}` is not a puzzle"
+        )));
+    };
+    let Some(solution) = aoc::find(day, part) else {
+        return Err(ServerFnError::ServerError(format!(
+            "day {day:02} part {part} is not solved yet"
+        )));
+    };
+    // some days take seconds, keep them off the async workers
+    // `name=value` pairs separated by spaces or commas, everything else keeps the puzzle's value
+    let (result, elapsed) = tokio::task::spawn_blocking(move || {
+        let start = std::time::Instant::now();
+        let result = aoc_core::params::parse_overrides(&params)
+            .and_then(|overrides| solution.process_with(&input, &overrides));
+        (result, start.elapsed())
+    })
+    .await?;
+    info!(day, part, ?elapsed);
+    Ok(match result {
+        Ok(answer) => Solved::Answer { answer, elapsed },
+        Err(error) => Solved::Failed(render_report(&error)),
+    })
+}
+
+#[cfg(feature = "ssr")]
+fn render_report(error: &aoc_core::AocError) -> String {
+    use miette::{GraphicalReportHandler, GraphicalTheme};
+
+    let mut report = String::new();
+    match GraphicalReportHandler::new_themed(GraphicalTheme::unicode_nocolor())
+        .render_report(&mut report, error)
+    {
+        Ok(()) => report,
+        Err(_) => error.to_string(),
+    }
 }
@@ -3,7 +3,7 @@ use leptos::*;
 #[tracing::instrument]
 #[component]
 pub fn IndexPage() -> impl IntoView {
-    let days = RwSignal::new(0..25);
+    let days = RwSignal::new(1..26);
     view! {
             <Hero/>
             <div